@@ -465,3 +465,2198 @@ describe("__eq", function()
         assert.are.not_equals(expected, vec);
     end);
 end)
+
+
+describe("map", function()
+    it("Returns a new Vector with the transformed elements", function()
+        local vec = vector.of(1, 2, 3);
+        local mapped = vec:map(function(value) return value * 10 end);
+
+        assert.are.equals(vector.of(10, 20, 30), mapped);
+        assert.are.equals(vector.of(1, 2, 3), vec);
+    end);
+
+    it("Passes the index as the second argument", function()
+        local vec = vector.of("a", "b", "c");
+        local mapped = vec:map(function(_, index) return index end);
+
+        assert.are.equals(vector.of(1, 2, 3), mapped);
+    end);
+
+    it("Propagates callback errors with the failing index", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.has.error_matches(function()
+            vec:map(function(value)
+                if value == 2 then error("boom") end
+                return value;
+            end);
+        end, "index 2");
+    end);
+end);
+
+describe("filter", function()
+    it("Keeps only the elements accepted by the predicate", function()
+        local vec = vector.of(1, 2, 3, 4, 5, 6);
+        local evens = vec:filter(function(value) return value % 2 == 0 end);
+
+        assert.are.equals(vector.of(2, 4, 6), evens);
+    end);
+
+    it("Treats nil results as rejection", function()
+        local vec = vector.of(1, 2, 3);
+        local filtered = vec:filter(function() return nil end);
+
+        assert.are.equals(0, #filtered);
+    end);
+end);
+
+describe("reduce", function()
+    it("Folds the elements starting from the given initial value", function()
+        local vec = vector.of(1, 2, 3, 4);
+        local sum = vec:reduce(function(acc, value) return acc + value end, 10);
+
+        assert.are.equals(20, sum);
+    end);
+
+    it("Uses the first element when no initial value is given", function()
+        local vec = vector.of("a", "b", "c");
+        local joined = vec:reduce(function(acc, value) return acc .. value end);
+
+        assert.are.equals("abc", joined);
+    end);
+
+    it("Returns nil for an empty Vector without initial value", function()
+        local vec = vector.new();
+
+        assert.are.equals(nil, vec:reduce(function(acc) return acc end));
+    end);
+
+    it("Accepts an explicit nil as initial value", function()
+        local vec = vector.of(1);
+        local first_acc = "unset";
+        vec:reduce(function(acc, value)
+            first_acc = acc;
+            return value;
+        end, nil);
+
+        assert.are.equals(nil, first_acc);
+    end);
+end);
+
+describe("find", function()
+    it("Returns a presence flag, the first matching element and its index", function()
+        local vec = vector.of(5, 8, 11, 14);
+
+        local is_present, value, index = vec:find(function(v) return v > 7 end);
+
+        assert.is.truthy(is_present);
+        assert.are.equals(8, value);
+        assert.are.equals(2, index);
+    end);
+
+    it("Returns a failure flag when nothing matches", function()
+        local vec = vector.of(1, 2, 3);
+
+        local is_present, value, index = vec:find(function(v) return v > 10 end);
+
+        assert.is.falsy(is_present);
+        assert.are.equals(nil, value);
+        assert.are.equals(nil, index);
+    end);
+end);
+
+describe("any", function()
+    it("Is true when at least one element matches", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.is.truthy(vec:any(function(v) return v == 2 end));
+    end);
+
+    it("Is false for an empty Vector", function()
+        assert.is.falsy(vector.new():any(function() return true end));
+    end);
+end);
+
+describe("all", function()
+    it("Is true when every element matches", function()
+        local vec = vector.of(2, 4, 6);
+
+        assert.is.truthy(vec:all(function(v) return v % 2 == 0 end));
+    end);
+
+    it("Is false when one element does not match", function()
+        local vec = vector.of(2, 3, 6);
+
+        assert.is.falsy(vec:all(function(v) return v % 2 == 0 end));
+    end);
+
+    it("Is true for an empty Vector", function()
+        assert.is.truthy(vector.new():all(function() return false end));
+    end);
+end);
+
+describe("for_each", function()
+    it("Visits every element in order", function()
+        local vec = vector.of("a", "b", "c");
+        local visited = {};
+
+        vec:for_each(function(value, index)
+            visited[index] = value;
+        end);
+
+        assert.are.same({ "a", "b", "c" }, visited);
+    end);
+end);
+
+describe("sort", function()
+    it("Sorts numbers in ascending order by default", function()
+        local vec = vector.of(3, 1.5, 2, -4);
+        vec:sort();
+
+        assert.are.equals(vector.of(-4, 1.5, 2, 3), vec);
+    end);
+
+    it("Sorts strings in ascending order by default", function()
+        local vec = vector.of("pear", "apple", "fig");
+        vec:sort();
+
+        assert.are.equals(vector.of("apple", "fig", "pear"), vec);
+    end);
+
+    it("Accepts a custom comparator", function()
+        local vec = vector.of(1, 3, 2);
+        vec:sort(function(a, b) return a > b end);
+
+        assert.are.equals(vector.of(3, 2, 1), vec);
+    end);
+
+    it("Causes error for elements that are not comparable", function()
+        local vec = vector.of(1, "two", 3);
+
+        assert.has.errors(function()
+            vec:sort();
+        end);
+        assert.are.equals(vector.of(1, "two", 3), vec);
+    end);
+
+    it("Causes error for an inconsistent comparator and leaves the Vector unchanged", function()
+        local vec = vector.of(2, 1, 2, 3);
+
+        assert.has.errors(function()
+            vec:sort(function(a, b) return a <= b end);
+        end);
+        assert.are.equals(vector.of(2, 1, 2, 3), vec);
+    end);
+end);
+
+describe("sort_stable", function()
+    it("Preserves the order of equal elements", function()
+        local vec = vector.of({ k = 2, id = "a" }, { k = 1, id = "b" }, { k = 2, id = "c" }, { k = 1, id = "d" });
+        vec:sort_stable(function(a, b) return a.k < b.k end);
+
+        local ids = vec:map(function(item) return item.id end);
+        assert.are.equals(vector.of("b", "d", "a", "c"), ids);
+    end);
+end);
+
+describe("sorted", function()
+    it("Returns a sorted copy without modifying the original", function()
+        local vec = vector.of(3, 1, 2);
+        local sorted = vec:sorted();
+
+        assert.are.equals(vector.of(1, 2, 3), sorted);
+        assert.are.equals(vector.of(3, 1, 2), vec);
+    end);
+end);
+
+describe("binary_search", function()
+    it("Returns a presence flag and the index of a found value", function()
+        local vec = vector.of(1, 3, 5, 7, 9);
+
+        local is_present, index = vec:binary_search(7);
+
+        assert.is.truthy(is_present);
+        assert.are.equals(4, index);
+    end);
+
+    it("Returns a failure flag and the insertion index of a missing value", function()
+        local vec = vector.of(1, 3, 5, 7, 9);
+
+        local is_present, index = vec:binary_search(6);
+
+        assert.is.falsy(is_present);
+        assert.are.equals(4, index);
+    end);
+
+    it("Accepts the comparator the Vector is sorted by", function()
+        local vec = vector.of(9, 7, 5, 3, 1);
+        local desc = function(a, b) return a > b end;
+
+        local is_present, index = vec:binary_search(3, desc);
+
+        assert.is.truthy(is_present);
+        assert.are.equals(4, index);
+    end);
+end);
+
+describe("partition_point", function()
+    it("Returns the index of the first rejected element", function()
+        local vec = vector.of(1, 2, 3, 10, 11);
+
+        assert.are.equals(4, vec:partition_point(function(v) return v < 5 end));
+    end);
+
+    it("Returns #vec + 1 when every element is accepted", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.are.equals(4, vec:partition_point(function() return true end));
+    end);
+end);
+
+describe("slice", function()
+    it("Returns a copy of the inclusive range", function()
+        local vec = vector.of(1, 2, 3, 4, 5);
+
+        assert.are.equals(vector.of(2, 3, 4), vec:slice(2, 4));
+    end);
+
+    it("Supports negative indexes", function()
+        local vec = vector.of(1, 2, 3, 4, 5);
+
+        assert.are.equals(vector.of(4, 5), vec:slice(-2));
+        assert.are.equals(vector.of(2, 3, 4), vec:slice(-4, -2));
+    end);
+
+    it("Clamps the range to the Vector bounds", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.are.equals(vector.of(1, 2, 3), vec:slice(-10, 10));
+        assert.are.equals(0, #vec:slice(3, 2));
+    end);
+
+    it("Is independent from the original Vector", function()
+        local vec = vector.of(1, 2, 3);
+        local copy = vec:slice(1, 2);
+        copy[1] = 100;
+
+        assert.are.equals(1, vec[1]);
+    end);
+end);
+
+describe("view", function()
+    it("Reads through to the parent Vector", function()
+        local vec = vector.of(1, 2, 3, 4, 5);
+        local view = vec:view(2, 4);
+
+        assert.are.equals(3, #view);
+        assert.are.equals(2, view[1]);
+        assert.are.equals(4, view[-1]);
+
+        vec[3] = "changed";
+        assert.are.equals("changed", view[2]);
+    end);
+
+    it("Writes through to the parent Vector", function()
+        local vec = vector.of(1, 2, 3, 4, 5);
+        local view = vec:view(-2);
+
+        view[1] = "a";
+        local succeed = view:set(2, "b");
+
+        assert.is.truthy(succeed);
+        assert.are.equals(vector.of(1, 2, 3, "a", "b"), vec);
+    end);
+
+    it("Uses presence flags in get and set", function()
+        local vec = vector.of(1, 2, 3);
+        local view = vec:view(1, 2);
+
+        local is_present, value = view:get(3);
+
+        assert.is.falsy(is_present);
+        assert.are.equals(nil, value);
+        assert.is.falsy(view:set(3, true));
+    end);
+
+    it("Causes error if indexed outside its bounds", function()
+        local vec = vector.of(1, 2, 3);
+        local view = vec:view(1, 2);
+
+        assert.has.errors(function()
+            local _ = view[3];
+        end);
+        assert.has.errors(function()
+            view[3] = true;
+        end);
+    end);
+
+    it("Shrinks along with its parent", function()
+        local vec = vector.of(1, 2, 3, 4, 5);
+        local view = vec:view(3, 5);
+
+        vec:pop();
+        vec:pop();
+
+        assert.are.equals(1, #view);
+        assert.has.errors(function()
+            local _ = view[2];
+        end);
+    end);
+
+    it("Can be copied into an independent Vector", function()
+        local vec = vector.of(1, 2, 3, 4);
+        local copy = vec:view(2, 3):to_vector();
+
+        assert.are.equals(vector.of(2, 3), copy);
+    end);
+end);
+
+describe("insert", function()
+    it("Inserts the value at the given index, shifting the following elements", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.is.truthy(vec:insert(2, "x"));
+        assert.are.equals(vector.of(1, "x", 2, 3), vec);
+    end);
+
+    it("Accepts #vec + 1 and -1 to append", function()
+        local vec = vector.of(1, 2);
+
+        vec:insert(#vec + 1, 3);
+        vec:insert(-1, 4);
+
+        assert.are.equals(vector.of(1, 2, 3, 4), vec);
+    end);
+
+    it("Returns false for an index outside the bounds", function()
+        local vec = vector.of(1, 2);
+
+        assert.is.falsy(vec:insert(4, "x"));
+        assert.is.falsy(vec:insert(0, "x"));
+        assert.are.equals(2, #vec);
+    end);
+end);
+
+describe("remove", function()
+    it("Removes and returns the element with a presence flag", function()
+        local vec = vector.of(1, 2, 3);
+
+        local is_present, value = vec:remove(-3);
+
+        assert.is.truthy(is_present);
+        assert.are.equals(1, value);
+        assert.are.equals(vector.of(2, 3), vec);
+    end);
+
+    it("Returns a failure flag for an index outside the bounds", function()
+        local vec = vector.of(1, 2, 3);
+
+        local is_present, value = vec:remove(4);
+
+        assert.is.falsy(is_present);
+        assert.are.equals(nil, value);
+        assert.are.equals(3, #vec);
+    end);
+end);
+
+describe("swap_remove", function()
+    it("Replaces the removed element with the last one", function()
+        local vec = vector.of(1, 2, 3, 4);
+
+        local is_present, value = vec:swap_remove(1);
+
+        assert.is.truthy(is_present);
+        assert.are.equals(1, value);
+        assert.are.equals(vector.of(4, 2, 3), vec);
+    end);
+end);
+
+describe("splice", function()
+    it("Replaces a range and returns the removed elements", function()
+        local vec = vector.of(1, 2, 3, 4, 5);
+
+        local succeed, removed = vec:splice(2, 2, "a", "b", "c");
+
+        assert.is.truthy(succeed);
+        assert.are.equals(vector.of(2, 3), removed);
+        assert.are.equals(vector.of(1, "a", "b", "c", 4, 5), vec);
+    end);
+
+    it("Clamps the count and accepts #vec + 1 as index", function()
+        local vec = vector.of(1, 2, 3);
+
+        local _, removed = vec:splice(-2, 10);
+        vec:splice(#vec + 1, 0, "end");
+
+        assert.are.equals(vector.of(2, 3), removed);
+        assert.are.equals(vector.of(1, "end"), vec);
+    end);
+
+    it("Returns a failure flag for an invalid index or count", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.is.falsy(vec:splice(5, 1));
+        assert.is.falsy(vec:splice(1, -1));
+        assert.are.equals(vector.of(1, 2, 3), vec);
+    end);
+end);
+
+describe("extend", function()
+    it("Appends the elements of another Vector", function()
+        local vec = vector.of(1, 2);
+
+        assert.is.truthy(vec:extend(vector.of(3, nil)));
+        assert.are.equals(vector.of(1, 2, 3, nil), vec);
+    end);
+
+    it("Appends the elements of a table", function()
+        local vec = vector.of(1);
+        vec:extend({ 2, 3 });
+
+        assert.are.equals(vector.of(1, 2, 3), vec);
+    end);
+
+    it("Can extend a Vector with itself", function()
+        local vec = vector.of(1, 2);
+        vec:extend(vec);
+
+        assert.are.equals(vector.of(1, 2, 1, 2), vec);
+    end);
+
+    it("Causes error for values that are neither Vectors nor tables", function()
+        assert.has.errors(function()
+            vector.new():extend(10);
+        end);
+    end);
+end);
+
+describe("truncate", function()
+    it("Keeps only the first elements", function()
+        local vec = vector.of(1, 2, 3, 4);
+        vec:truncate(2);
+
+        assert.are.equals(vector.of(1, 2), vec);
+    end);
+
+    it("Has no effect for a length greater than the Vector's", function()
+        local vec = vector.of(1, 2);
+        vec:truncate(10);
+
+        assert.are.equals(2, #vec);
+    end);
+
+    it("Causes error for a negative length", function()
+        assert.has.errors(function()
+            vector.of(1):truncate(-1);
+        end);
+    end);
+end);
+
+describe("clear", function()
+    it("Removes every element", function()
+        local vec = vector.of(1, 2, 3);
+        vec:clear();
+
+        assert.are.equals(0, #vec);
+    end);
+end);
+
+describe("swap", function()
+    it("Swaps two elements", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.is.truthy(vec:swap(1, -1));
+        assert.are.equals(vector.of(3, 2, 1), vec);
+    end);
+
+    it("Returns false for an index outside the bounds", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.is.falsy(vec:swap(1, 4));
+        assert.are.equals(vector.of(1, 2, 3), vec);
+    end);
+end);
+
+describe("reverse", function()
+    it("Reverses the elements in place", function()
+        local vec = vector.of(1, 2, 3);
+        vec:reverse();
+
+        assert.are.equals(vector.of(3, 2, 1), vec);
+    end);
+end);
+
+describe("rotate", function()
+    it("Rotates towards the end for positive amounts", function()
+        local vec = vector.of(1, 2, 3, 4);
+        vec:rotate(1);
+
+        assert.are.equals(vector.of(4, 1, 2, 3), vec);
+    end);
+
+    it("Rotates towards the start for negative amounts", function()
+        local vec = vector.of(1, 2, 3, 4);
+        vec:rotate(-5);
+
+        assert.are.equals(vector.of(2, 3, 4, 1), vec);
+    end);
+
+    it("Has no effect on an empty Vector", function()
+        local vec = vector.new();
+        vec:rotate(3);
+
+        assert.are.equals(0, #vec);
+    end);
+end);
+
+describe("iter", function()
+    it("Visits every index and value in order", function()
+        local vec = vector.of("a", nil, "c");
+        local indexes, values = {}, {};
+
+        for i, value in vec:iter() do
+            table.insert(indexes, i);
+            values[i] = value;
+        end
+
+        assert.are.same({ 1, 2, 3 }, indexes);
+        assert.are.same({ "a", nil, "c" }, values);
+    end);
+
+    it("Allows elements to be replaced during iteration", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.has.no_errors(function()
+            for i, value in vec:iter() do
+                vec[i] = value * 2;
+            end
+        end);
+        assert.are.equals(vector.of(2, 4, 6), vec);
+    end);
+
+    it("Causes error if the Vector is structurally modified during iteration", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.has.errors(function()
+            for _, value in vec:iter() do
+                vec:push(value);
+            end
+        end);
+    end);
+end);
+
+describe("iter_rev", function()
+    it("Visits every element from last to first with the original indexes", function()
+        local vec = vector.of("a", "b", "c");
+        local visited = {};
+
+        for i, value in vec:iter_rev() do
+            table.insert(visited, i .. value);
+        end
+
+        assert.are.same({ "3c", "2b", "1a" }, visited);
+    end);
+
+    it("Causes error if the Vector is structurally modified during iteration", function()
+        local vec = vector.of(1, 2, 3);
+
+        assert.has.errors(function()
+            for _ in vec:iter_rev() do
+                vec:pop();
+            end
+        end);
+    end);
+end);
+
+describe("enumerate", function()
+    it("Yields a counter starting at the given value", function()
+        local vec = vector.of("a", "b");
+        local visited = {};
+
+        for n, value in vec:enumerate(0) do
+            visited[value] = n;
+        end
+
+        assert.are.same({ a = 0, b = 1 }, visited);
+    end);
+end);
+
+describe("__call", function()
+    it("Lets the Vector be used directly in a generic for", function()
+        local vec = vector.of(10, 20, 30);
+        local sum = 0;
+
+        for _, value in vec do
+            sum = sum + value;
+        end
+
+        assert.are.equals(60, sum);
+    end);
+end);
+
+describe("typed vectors", function()
+    it("Are created with a size and a numeric filler", function()
+        local vec = vector.f64(3, 1.5);
+
+        assert.are.equals(3, #vec);
+        assert.are.equals(1.5, vec[3]);
+        assert.are.equals("f64", vec:element_type());
+    end);
+
+    it("Are filled with zeros by default", function()
+        local vec = vector.i64(2);
+
+        assert.are.equals(0, vec[1]);
+        assert.are.equals(0, vec[-1]);
+    end);
+
+    it("Share the presence flag conventions of Vector", function()
+        local vec = vector.i32(1);
+
+        local is_present, value = vec:get(2);
+        assert.is.falsy(is_present);
+        assert.are.equals(nil, value);
+
+        assert.is.falsy(vec:set(2, 10));
+        assert.is.truthy(vec:push(10));
+
+        local popped, last = vec:pop();
+        assert.is.truthy(popped);
+        assert.are.equals(10, last);
+    end);
+
+    it("Cause error when a non-numeric value is written", function()
+        local vec = vector.f64(2);
+
+        assert.has.errors(function() vec:set(1, "one") end);
+        assert.has.errors(function() vec:push(true) end);
+        assert.has.errors(function() vec[1] = {} end);
+    end);
+
+    it("Cause error when a number cannot be stored exactly", function()
+        local ints = vector.i64(1);
+        local small = vector.i32(1);
+
+        assert.has.errors(function() ints[1] = 1.5 end);
+        assert.has.errors(function() small[1] = 2 ^ 40 end);
+        assert.has.no_errors(function() ints[1] = 2.0 end);
+    end);
+
+    it("Accept large numbers only where they are exact", function()
+        assert.are.equals(2 ^ 60, vector.f64_from({ 2 ^ 60 })[1]);
+        assert.are.equals(2 ^ 53 + 2, vector.f64_from({ 2 ^ 53 + 2 })[1]);
+        assert.has.errors(function() vector.i64_from({ 2 ^ 63 }) end);
+        assert.has.errors(function() vector.i64_from({ -2 ^ 64 }) end);
+        assert.has.no_errors(function() vector.i64_from({ -2 ^ 63 }) end);
+    end);
+
+    it("Reject sizes that cannot be allocated", function()
+        assert.has.error_matches(function() vector.f64(2 ^ 53) end, "Cannot allocate memory");
+        assert.has.error_matches(function() vector.i32(2 ^ 62) end, "Cannot allocate memory");
+    end);
+
+    it("Round numbers to the nearest f32", function()
+        local singles = vector.f32(1, 0.1);
+        singles:push(0.1);
+
+        assert.are.equals(0.5, vector.f32_from({ 0.5 })[1]);
+        assert.are.equals(singles[1], vector.f32_from({ 0.1 })[1]);
+        assert.are.equals(singles[1], singles[2]);
+        assert.is_true(math.abs(singles[1] - 0.1) < 1e-8);
+        assert.has.error_matches(function() vector.f32_from({ 1e300 }) end, "no exact f32 representation");
+        assert.has.no_errors(function() vector.f32_from({ 0 / 0, 1 / 0 }) end);
+    end);
+
+    it("Convert to and from a generic Vector", function()
+        local vec = vector.of(1, 2, 3);
+        local typed = vector.i64_from(vec);
+
+        assert.are.equals(vector.i64_from({ 1, 2, 3 }), typed);
+        assert.are.equals(vec, typed:to_vector());
+    end);
+
+    it("Cause error when converting non-numeric elements", function()
+        assert.has.errors(function()
+            vector.f64_from(vector.of(1, "two"));
+        end);
+    end);
+
+    it("Are only equal to typed vectors of the same element type", function()
+        assert.are.equals(vector.f64(2, 1), vector.f64(2, 1));
+        assert.are.not_equals(vector.f64(2, 1), vector.i64(2, 1));
+    end);
+end);
+
+describe("arithmetic metamethods", function()
+    it("Operate element-wise on Vectors of the same length", function()
+        local a = vector.of(1, 2, 3);
+        local b = vector.of(4, 5, 6);
+
+        assert.are.equals(vector.of(5, 7, 9), a + b);
+        assert.are.equals(vector.of(3, 3, 3), b - a);
+        assert.are.equals(vector.of(4, 10, 18), a * b);
+        assert.are.equals(vector.of(4, 2.5, 2), b / a);
+    end);
+
+    it("Broadcast a scalar on either side", function()
+        local vec = vector.of(1, 2, 4);
+
+        assert.are.equals(vector.of(2, 4, 8), vec * 2);
+        assert.are.equals(vector.of(2, 4, 8), 2 * vec);
+        assert.are.equals(vector.of(9, 8, 6), 10 - vec);
+        assert.are.equals(vector.of(4, 2, 1), 4 / vec);
+    end);
+
+    it("Negate every element", function()
+        assert.are.equals(vector.of(-1, 2, -3), -vector.of(1, -2, 3));
+    end);
+
+    it("Cause error for Vectors of different lengths", function()
+        assert.has.error_matches(function()
+            local _ = vector.of(1, 2) + vector.of(1, 2, 3);
+        end, "different lengths");
+    end);
+
+    it("Cause error for non-numeric elements", function()
+        assert.has.error_matches(function()
+            local _ = vector.of(1, "two") * 2;
+        end, "index 2");
+        assert.has.errors(function()
+            local _ = vector.of(1, 2) + "three";
+        end);
+    end);
+end);
+
+describe("linear algebra helpers", function()
+    it("dot computes the dot product", function()
+        assert.are.equals(32, vector.of(1, 2, 3):dot(vector.of(4, 5, 6)));
+    end);
+
+    it("norm computes the Euclidean length", function()
+        assert.are.equals(5, vector.of(3, 4):norm());
+    end);
+
+    it("normalize returns a Vector with norm 1", function()
+        assert.are.equals(vector.of(0.6, 0.8), vector.of(3, 4):normalize());
+
+        assert.has.errors(function()
+            vector.of(0, 0):normalize();
+        end);
+    end);
+
+    it("cross computes the cross product of 3D Vectors", function()
+        local x = vector.of(1, 0, 0);
+        local y = vector.of(0, 1, 0);
+
+        assert.are.equals(vector.of(0, 0, 1), x:cross(y));
+        assert.has.errors(function()
+            vector.of(1, 2):cross(vector.of(3, 4));
+        end);
+    end);
+
+    it("sum, min, max and mean summarize the elements", function()
+        local vec = vector.of(4, -2, 10, 8);
+
+        assert.are.equals(20, vec:sum());
+        assert.are.equals(-2, vec:min());
+        assert.are.equals(10, vec:max());
+        assert.are.equals(5, vec:mean());
+    end);
+
+    it("norm, normalize and mean do not overflow on large integers", function()
+        assert.are.equals(4294967296, vector.of(4294967296, 0):norm());
+        assert.are.equals(vector.of(1, 0), vector.of(4294967296, 0):normalize());
+        assert.is_true(vector.of(2 ^ 62, 2 ^ 62):mean() > 0);
+    end);
+
+    it("negation turns a float zero into a negative zero", function()
+        assert.are.equals(-math.huge, 1 / (-vector.of(0.0))[1]);
+    end);
+
+    it("sum is 0 while min, max and mean are nil for an empty Vector", function()
+        local vec = vector.new();
+
+        assert.are.equals(0, vec:sum());
+        assert.are.equals(nil, vec:min());
+        assert.are.equals(nil, vec:max());
+        assert.are.equals(nil, vec:mean());
+    end);
+
+    it("Cause error for non-numeric elements", function()
+        assert.has.errors(function()
+            vector.of(1, {}):sum();
+        end);
+        assert.has.errors(function()
+            vector.of(1, false):max();
+        end);
+    end);
+end);
+
+describe("from_table", function()
+    it("Creates a Vector with the elements of a sequence", function()
+        assert.are.equals(vector.of(1, 2, 3), vector.from_table({ 1, 2, 3 }));
+    end);
+
+    it("Keeps holes up to an explicit length", function()
+        local vec = vector.from_table({ 1, nil, 3 }, 4);
+
+        assert.are.equals(vector.of(1, nil, 3, nil), vec);
+    end);
+
+    it("Causes error for a negative length", function()
+        assert.has.errors(function()
+            vector.from_table({}, -1);
+        end);
+    end);
+end);
+
+describe("to_table", function()
+    it("Returns a table with the elements and the length", function()
+        local t, n = vector.of("a", nil, "c", nil):to_table();
+
+        assert.are.same({ "a", nil, "c" }, t);
+        assert.are.equals(4, n);
+    end);
+
+    it("Round-trips through from_table", function()
+        local vec = vector.of(nil, 2, nil);
+
+        assert.are.equals(vec, vector.from_table(vec:to_table()));
+    end);
+end);
+
+describe("unpack", function()
+    it("Returns every element as multiple values", function()
+        local a, b, c = vector.of(1, 2, 3):unpack();
+
+        assert.are.same({ 1, 2, 3 }, { a, b, c });
+    end);
+
+    it("Returns the elements of a range", function()
+        local a, b, c = vector.of(1, 2, 3, 4):unpack(2, -2);
+
+        assert.are.same({ 2, 3 }, { a, b, c });
+    end);
+end);
+
+describe("__tostring", function()
+    it("Renders the elements", function()
+        assert.are.equals('Vector(1, "two", nil, true)', tostring(vector.of(1, "two", nil, true)));
+    end);
+
+    it("Renders nested Vectors and breaks cycles", function()
+        local vec = vector.of(1, vector.of(2));
+        vec:push(vec);
+
+        assert.are.equals("Vector(1, Vector(2), Vector(<cycle>))", tostring(vec));
+    end);
+
+    it("Truncates long Vectors", function()
+        local rendered = tostring(vector.new(100, 0));
+
+        assert.is.truthy(rendered:find("... (68 more)", 1, true));
+    end);
+end);
+
+describe("__concat", function()
+    it("Creates a new Vector with the elements of both operands", function()
+        local a = vector.of(1, 2);
+        local b = vector.of(3);
+
+        assert.are.equals(vector.of(1, 2, 3), a .. b);
+        assert.are.equals(vector.of(1, 2), a);
+    end);
+
+    it("Accepts tables on either side", function()
+        assert.are.equals(vector.of(0, 1, 2), { 0 } .. vector.of(1, 2));
+        assert.are.equals(vector.of(1, 2, 3), vector.of(1) .. { 2, 3 });
+    end);
+end);
+
+describe("to_json and from_json", function()
+    it("Round-trips the elements, keeping explicit nils", function()
+        local vec = vector.of(1, nil, "two", true, 2.5, nil);
+        local json = vec:to_json();
+
+        assert.are.equals('[1,null,"two",true,2.5,null]', json);
+        assert.are.equals(vec, vector.from_json(json));
+        assert.are.equals(6, #vector.from_json(json));
+    end);
+
+    it("Encodes nested Vectors and tables", function()
+        local vec = vector.of(vector.of(1, 2), { 3, 4 }, { key = "value" });
+
+        assert.are.equals('[[1,2],[3,4],{"key":"value"}]', vec:to_json());
+    end);
+
+    it("Decodes nested arrays as Vectors and objects as tables", function()
+        local vec = vector.from_json('[[1, [2]], {"key": null, "other": 3}]');
+
+        assert.are.equals(1, vec[1][1]);
+        assert.are.equals(vector.of(2), vec[1][2]);
+        assert.are.equals(3, vec[2].other);
+    end);
+
+    it("Causes an error for functions, userdata and cycles", function()
+        local cyclic = vector.of(1);
+        cyclic:push(cyclic);
+
+        assert.has.errors(function() vector.of(print):to_json() end);
+        assert.has.errors(function() vector.of(vector.f64(1, 0)):to_json() end);
+        assert.has.errors(function() cyclic:to_json() end);
+    end);
+
+    it("Causes an error for invalid JSON or a top-level value that is not an array", function()
+        assert.has.errors(function() vector.from_json("[1,") end);
+        assert.has.errors(function() vector.from_json('{"a": 1}') end);
+    end);
+end);
+
+describe("to_msgpack and from_msgpack", function()
+    it("Round-trips the elements, keeping explicit nils", function()
+        local vec = vector.of(1, nil, "two", nil, 0.5);
+        local nested = vector.from_msgpack(vector.of(vector.of(false, nil)):to_msgpack());
+
+        assert.are.equals(vec, vector.from_msgpack(vec:to_msgpack()));
+        assert.are.equals(vector.of(false, nil), nested[1]);
+    end);
+
+    it("Round-trips tables and binary strings", function()
+        local vec = vector.from_msgpack(vector.of({ x = 1 }, "\255\0"):to_msgpack());
+
+        assert.are.equals(1, vec[1].x);
+        assert.are.equals("\255\0", vec[2]);
+    end);
+
+    it("Causes an error for malformed bytes", function()
+        assert.has.errors(function() vector.from_msgpack("\145") end);
+        assert.has.errors(function() vector.from_msgpack(vector.of(1):to_msgpack() .. "x") end);
+    end);
+end);
+
+describe("Capacity", function()
+    it("Creates an empty Vector with the requested capacity", function()
+        local vec = vector.with_capacity(100);
+
+        assert.are.equals(0, #vec);
+        assert.is.truthy(vec:capacity() >= 100);
+    end);
+
+    it("Reserves room for more elements and shrinks back", function()
+        local vec = vector.of(1, 2, 3);
+        vec:reserve(50);
+
+        assert.is.truthy(vec:capacity() >= 53);
+        assert.are.equals(vector.of(1, 2, 3), vec);
+
+        vec:shrink_to_fit();
+        assert.are.equals(3, vec:capacity());
+    end);
+
+    it("Causes an error for negative or huge capacities instead of aborting", function()
+        local vec = vector.of(1);
+
+        assert.has.errors(function() vector.with_capacity(-1) end);
+        assert.has.errors(function() vector.with_capacity(2 ^ 53) end);
+        assert.has.errors(function() vector.new(2 ^ 53) end);
+        assert.has.errors(function() vec:reserve(2 ^ 53) end);
+        assert.are.equals(vector.of(1), vec);
+    end);
+end);
+
+describe("Deque", function()
+    it("Pushes and pops at both ends", function()
+        local deque = vector.deque(2);
+        deque:push(3);
+        deque:push_front(1);
+
+        assert.are.equals(vector.of(1, 2, 3), deque:to_vector());
+        assert.are.same({ true, 1 }, { deque:pop_front() });
+        assert.are.same({ true, 3 }, { deque:pop() });
+        assert.are.equals(1, #deque);
+    end);
+
+    it("Peeks without removing and reports absence on an empty deque", function()
+        local deque = vector.deque("a", "b");
+
+        assert.are.same({ true, "a" }, { deque:peek_front() });
+        assert.are.same({ true, "b" }, { deque:peek_back() });
+        assert.are.equals(2, #deque);
+
+        local empty = vector.deque();
+        assert.are.same({ false, nil }, { empty:peek_front() });
+        assert.are.same({ false, nil }, { empty:pop_front() });
+        assert.are.same({ false, nil }, { empty:pop() });
+    end);
+
+    it("Indexes like a Vector, relative to the front", function()
+        local deque = vector.deque(2, 3);
+        deque:push_front(1);
+
+        assert.are.equals(1, deque[1]);
+        assert.are.equals(3, deque[-1]);
+        assert.are.same({ false, nil }, { deque:get(4) });
+
+        deque[2] = "two";
+        assert.are.equals("two", deque[2]);
+        assert.is.falsy(deque:set(0, 1));
+        assert.has.errors(function() return deque[4] end);
+        assert.has.errors(function() deque[4] = 1 end);
+    end);
+
+    it("Keeps explicit nils", function()
+        local deque = vector.deque(nil);
+        deque:push_front(nil);
+
+        assert.are.equals(2, #deque);
+        assert.are.same({ true, nil }, { deque:get(1) });
+    end);
+
+    it("Compares by value", function()
+        local a = vector.deque(1, 2);
+        local b = vector.deque(2);
+        b:push_front(1);
+
+        assert.are.equals(a, b);
+        assert.are.not_equals(a, vector.deque(1));
+        assert.is.falsy(a:is_same(b));
+        assert.is.truthy(a:is_same(a));
+    end);
+end);
+
+describe("Ring", function()
+    it("Overwrites the oldest element once full", function()
+        local ring = vector.ring(3);
+
+        assert.are.same({ false, nil }, { ring:push(1) });
+        ring:push(2);
+        assert.is.falsy(ring:is_full());
+        ring:push(3);
+        assert.is.truthy(ring:is_full());
+
+        assert.are.same({ true, 1 }, { ring:push(4) });
+        assert.are.equals(vector.of(2, 3, 4), ring:to_vector());
+        assert.are.equals(3, #ring);
+        assert.are.equals(3, ring:capacity());
+    end);
+
+    it("Reports an evicted nil as present", function()
+        local ring = vector.ring(1);
+        ring:push(nil);
+
+        assert.are.same({ true, nil }, { ring:push(1) });
+    end);
+
+    it("Indexes relative to the oldest element", function()
+        local ring = vector.ring(2);
+        ring:push("a");
+        ring:push("b");
+        ring:push("c");
+
+        assert.are.equals("b", ring[1]);
+        assert.are.equals("c", ring[-1]);
+        assert.are.same({ false, nil }, { ring:get(3) });
+
+        ring[1] = "B";
+        assert.are.equals("B", ring[1]);
+        assert.has.errors(function() return ring[3] end);
+        assert.has.errors(function() ring[3] = 1 end);
+    end);
+
+    it("Clears the elements and keeps the capacity", function()
+        local ring = vector.ring(2);
+        ring:push(1);
+        ring:clear();
+
+        assert.are.equals(0, #ring);
+        assert.are.equals(2, ring:capacity());
+    end);
+
+    it("Iterates from the oldest to the newest element", function()
+        local ring = vector.ring(3);
+        for i = 1, 5 do ring:push(i) end;
+
+        local seen = {};
+        for i, value in ring:iter() do seen[i] = value end;
+
+        assert.are.same({ 3, 4, 5 }, seen);
+    end);
+
+    it("Causes an error if modified during iteration", function()
+        local ring = vector.ring(3);
+        ring:push(1);
+        ring:push(2);
+
+        assert.has.errors(function()
+            for _, value in ring:iter() do ring:push(value) end;
+        end);
+    end);
+
+    it("Causes an error for a capacity that is not positive", function()
+        assert.has.errors(function() vector.ring(0) end);
+    end);
+end);
+
+describe("deep_equals", function()
+    it("Compares nested Vectors and tables by content", function()
+        local a = vector.of(1, vector.of(2, { x = { 3 } }), nil);
+        local b = vector.of(1, vector.of(2, { x = { 3 } }), nil);
+
+        assert.are.not_equals(a, b);
+        assert.is.truthy(a:deep_equals(b));
+    end);
+
+    it("Detects differences in nested content", function()
+        assert.is.falsy(vector.of({ x = 1 }):deep_equals(vector.of({ x = 2 })));
+        assert.is.falsy(vector.of({ x = 1 }):deep_equals(vector.of({ x = 1, y = 2 })));
+        assert.is.falsy(vector.of(vector.of(1)):deep_equals(vector.of(vector.of(1, nil))));
+        assert.is.falsy(vector.of(1):deep_equals({ 1 }));
+    end);
+
+    it("Terminates on cyclic structures", function()
+        local a = vector.of(1);
+        a:push(a);
+        local b = vector.of(1);
+        b:push(b);
+
+        assert.is.truthy(a:deep_equals(b));
+    end);
+end);
+
+describe("__lt and __le", function()
+    it("Orders Vectors lexicographically", function()
+        assert.is.truthy(vector.of(1, 2) < vector.of(1, 3));
+        assert.is.truthy(vector.of(1, 2) < vector.of(1, 2, 0));
+        assert.is.falsy(vector.of(2) < vector.of(1, 5));
+        assert.is.truthy(vector.of("a", 1) <= vector.of("a", 1));
+        assert.is.falsy(vector.of("b") <= vector.of("a"));
+    end);
+
+    it("Compares nested Vectors recursively", function()
+        assert.is.truthy(vector.of(vector.of(1, 2)) < vector.of(vector.of(1, 3)));
+    end);
+
+    it("Allows sorting Vectors of Vectors", function()
+        local vec = vector.of(vector.of(2, 1), vector.of(1, 2), vector.of(1));
+        vec:sort(function(a, b) return a < b end);
+
+        assert.are.equals(vector.of(1), vec[1]);
+        assert.are.equals(vector.of(2, 1), vec[3]);
+    end);
+
+    it("Sorts Vectors of Vectors with the default ordering", function()
+        local vec = vector.of(vector.of(2), vector.of(1, 5), vector.of(1));
+
+        vec:sort();
+
+        assert.is_true(vec:deep_equals(vector.of(vector.of(1), vector.of(1, 5), vector.of(2))));
+        assert.are.equals(vector.of(1), vector.of(vector.of(2), vector.of(1)):sorted()[1]);
+    end);
+
+    it("Causes an error for elements that cannot be ordered", function()
+        assert.has.errors(function() return vector.of(true) < vector.of(false) end);
+        assert.has.errors(function() return vector.of(1) < vector.of("1") end);
+    end);
+end);
+
+describe("Searching and counting", function()
+    it("Checks whether a value is contained", function()
+        local vec = vector.of(1, "two", nil);
+
+        assert.is.truthy(vec:contains("two"));
+        assert.is.truthy(vec:contains(nil));
+        assert.is.truthy(vec:contains(1.0));
+        assert.is.falsy(vec:contains(3));
+    end);
+
+    it("Finds the first and last indices of a value", function()
+        local vec = vector.of("a", "b", "a", "b");
+
+        assert.are.equals(1, vec:index_of("a"));
+        assert.are.equals(3, vec:index_of("a", 2));
+        assert.are.equals(3, vec:index_of("a", -2));
+        assert.are.equals(4, vec:last_index_of("b"));
+        assert.is.falsy(vec:index_of("a", 4));
+        assert.is.falsy(vec:last_index_of("c"));
+    end);
+
+    it("Matches tables by reference", function()
+        local t = {};
+        local vec = vector.of({}, t);
+
+        assert.are.equals(2, vec:index_of(t));
+        assert.is.falsy(vec:contains({}));
+    end);
+
+    it("Counts values or elements accepted by a predicate", function()
+        local vec = vector.of(1, 2, 1, 3, nil);
+
+        assert.are.equals(2, vec:count(1));
+        assert.are.equals(1, vec:count(nil));
+        assert.are.equals(2, vec:count(function(value) return value and value > 1 end));
+    end);
+
+    it("Keeps the first occurrence of every element", function()
+        local vec = vector.of(3, 1, 3, 2.0, 1, 2, nil, nil);
+
+        assert.are.equals(vector.of(3, 1, 2.0, nil), vec:unique());
+        assert.are.equals(8, #vec);
+    end);
+
+    it("Removes consecutive duplicates in place", function()
+        local vec = vector.of(1, 1, 2, 1, nil, nil, 3, 3);
+        vec:dedup();
+
+        assert.are.equals(vector.of(1, 2, 1, nil, 3), vec);
+    end);
+end);
+
+describe("Frozen Vectors", function()
+    it("Rejects every mutator", function()
+        local vec = vector.of(3, 1, 2);
+        vec:freeze();
+
+        assert.is.truthy(vec:is_frozen());
+        assert.error_matches(function() vec:set(1, 0) end, "frozen");
+        assert.error_matches(function() vec[1] = 0 end, "frozen");
+        assert.has.errors(function() vec:push(4) end);
+        assert.has.errors(function() vec:pop() end);
+        assert.has.errors(function() vec:sort() end);
+        assert.has.errors(function() vec:insert(1, 0) end);
+        assert.has.errors(function() vec:remove(1) end);
+        assert.has.errors(function() vec:extend({ 4 }) end);
+        assert.has.errors(function() vec:clear() end);
+        assert.has.errors(function() vec:reverse() end);
+        assert.are.equals(vector.of(3, 1, 2), vec);
+    end);
+
+    it("Rejects writes through views", function()
+        local vec = vector.frozen_of(1, 2);
+        local view = vec:view();
+
+        assert.has.errors(function() view:set(1, 0) end);
+        assert.has.errors(function() view[1] = 0 end);
+        assert.are.equals(1, view[1]);
+    end);
+
+    it("Allows non-mutating methods", function()
+        local vec = vector.frozen_of(3, 1, 2);
+
+        assert.are.equals(vector.of(1, 2, 3), vec:sorted());
+        assert.is.falsy(vec:sorted():is_frozen());
+        assert.are.equals(vector.of(6, 2, 4), vec:map(function(value) return value * 2 end));
+        assert.are.equals(3, #vec);
+    end);
+
+    it("Returns mutable copies with thaw_copy", function()
+        local vec = vector.frozen_of(1);
+        local copy = vec:thaw_copy();
+        copy:push(2);
+
+        assert.is.falsy(copy:is_frozen());
+        assert.are.equals(vector.of(1, 2), copy);
+        assert.are.equals(vector.of(1), vec);
+    end);
+end);
+
+describe("Persistent Vectors", function()
+    it("Returns new versions and leaves the previous ones unchanged", function()
+        local v1 = vector.persistent({ 1, 2 });
+        local v2 = v1:push(3);
+        local v3 = v2:set(1, "one");
+
+        assert.are.equals(vector.of(1, 2), v1:to_vector());
+        assert.are.equals(vector.of(1, 2, 3), v2:to_vector());
+        assert.are.equals(vector.of("one", 2, 3), v3:to_vector());
+        assert.are.equals(3, #v3);
+        assert.are.equals(3, v3[-1]);
+    end);
+
+    it("Pops the last element into a new version", function()
+        local vec = vector.persistent(vector.of(1, nil));
+        local ok, value, rest = vec:pop();
+
+        assert.is.truthy(ok);
+        assert.are.equals(nil, value);
+        assert.are.equals(1, #rest);
+        assert.are.equals(2, #vec);
+
+        local empty_ok, _, empty = vector.persistent():pop();
+        assert.is.falsy(empty_ok);
+        assert.are.equals(0, #empty);
+    end);
+
+    it("Keeps versions consistent across many levels of the trie", function()
+        local versions = { vector.persistent() };
+        for i = 1, 2000 do versions[i + 1] = versions[i]:push(i) end;
+
+        local last = versions[2001];
+        local updated = last:set(1500, "x");
+        assert.are.equals(1500, last[1500]);
+        assert.are.equals("x", updated[1500]);
+        assert.are.equals(1000, #versions[1001]);
+        assert.are.equals(1000, versions[1001][-1]);
+
+        local vec = updated;
+        for _ = 1, 1990 do
+            local _, _, rest = vec:pop();
+            vec = rest;
+        end;
+        assert.are.equals(vector.of(1, 2, 3, 4, 5, 6, 7, 8, 9, 10), vec:to_vector());
+    end);
+
+    it("Converts from and to mutable Vectors", function()
+        local mutable = vector.of(1, 2);
+        local vec = mutable:to_persistent();
+        mutable:push(3);
+
+        assert.are.equals(2, #vec);
+        assert.are.equals(vector.of(1, 2), vec:to_vector());
+    end);
+
+    it("Compares by value and rejects assignments", function()
+        local vec = vector.persistent({ 1, 2 });
+
+        assert.are.equals(vec, vector.persistent():push(1):push(2));
+        assert.are.not_equals(vec, vec:set(1, 0));
+        assert.has.errors(function() vec[1] = 0 end);
+        assert.has.errors(function() vec:set(3, 0) end);
+        assert.are.same({ false, nil }, { vec:get(3) });
+    end);
+end);
+
+describe("Version-specific capabilities", function()
+    if _VERSION ~= "Lua 5.1" then
+        it("Iterates with pairs", function()
+            local seen = {};
+            for i, value in pairs(vector.of("a", "b")) do seen[i] = value end;
+
+            assert.are.same({ "a", "b" }, seen);
+        end);
+    end;
+
+    if _VERSION == "Lua 5.2" or _VERSION == "Lua 5.3" then
+        it("Iterates with ipairs", function()
+            local seen = {};
+            for i, value in ipairs(vector.of("a", "b")) do seen[i] = value end;
+
+            assert.are.same({ "a", "b" }, seen);
+        end);
+    else
+        it("Does not support ipairs", function()
+            assert.has.errors(function()
+                for _ in ipairs(vector.of("a", "b")) do end;
+            end);
+        end);
+    end;
+
+    if math.type then
+        it("Keeps the integer subtype in arithmetic", function()
+            local sum = vector.of(1, 2) + vector.of(3, 4);
+
+            assert.are.equals("integer", math.type(sum[1]));
+            assert.are.equals("float", math.type((vector.of(4) / 2)[1]));
+        end);
+    end;
+
+    if _VERSION == "Lua 5.4" then
+        it("Releases the spare capacity of a to-be-closed Vector", function()
+            local captured;
+            load([[
+                local vector, capture = ...;
+                local vec <close> = vector.with_capacity(100);
+                vec:extend({ 1, 2, 3 });
+                capture(vec);
+            ]])(vector, function(vec) captured = vec end);
+
+            assert.are.same({ 1, 2, 3 }, captured:to_table());
+            assert.is_true(captured:capacity() < 100);
+        end);
+    end;
+
+    it("Does not wrap integral floats on overflow", function()
+        local big = 2 ^ 62;
+
+        assert.are.equals(2 ^ 63, (vector.of(big) + vector.of(big))[1]);
+    end);
+end);
+
+describe("Lazy iterators", function()
+    it("Evaluates a pipeline only in terminal operations", function()
+        local calls = 0;
+        local lazy = vector.of(1, 2, 3, 4, 5, 6):lazy()
+            :map(function(value) calls = calls + 1; return value * 10 end)
+            :filter(function(value) return value > 20 end)
+            :take(2);
+
+        assert.are.equals(0, calls);
+        assert.are.same({ 30, 40 }, lazy:collect():to_table());
+        assert.are.equals(4, calls);
+    end);
+
+    it("Stops evaluating once first finds an item", function()
+        local calls = 0;
+        local found, value = vector.of(1, 2, 3):lazy()
+            :map(function(value) calls = calls + 1; return value end)
+            :first();
+
+        assert.is_true(found);
+        assert.are.equals(1, value);
+        assert.are.equals(1, calls);
+        assert.is_false(vector.new():lazy():first());
+    end);
+
+    it("Passes multiple values from enumerate and zip", function()
+        local enumerated = vector.of("a", "b", "c"):lazy():enumerate():collect();
+        local zipped = vector.of(1, 2, 3):lazy()
+            :zip({ 10, 20 })
+            :map(function(a, b) return a + b end)
+            :collect();
+
+        assert.are.same({ 2, "b" }, enumerated[2]:to_table());
+        assert.are.same({ 11, 22 }, zipped:to_table());
+    end);
+
+    it("Chains, skips and steps", function()
+        local vec = vector.of(1, 2, 3):lazy()
+            :chain(vector.of(4, 5, 6):lazy())
+            :skip(1)
+            :step_by(2)
+            :collect();
+
+        assert.are.same({ 2, 4, 6 }, vec:to_table());
+    end);
+
+    it("Takes and skips while a predicate holds", function()
+        local small = function(value) return value < 3 end;
+
+        assert.are.same({ 1, 2 }, vector.of(1, 2, 3, 1):lazy():take_while(small):collect():to_table());
+        assert.are.same({ 3, 1 }, vector.of(1, 2, 3, 1):lazy():skip_while(small):collect():to_table());
+    end);
+
+    it("Folds, counts and finds the last item", function()
+        local add = function(acc, value) return acc + value end;
+
+        assert.are.equals(10, vector.of(1, 2, 3, 4):lazy():fold(add, 0));
+        assert.are.equals(">ab", vector.of("a", "b"):lazy():fold(function(acc, v) return acc .. v end, ">"));
+        assert.are.equals(2, vector.of(1, 2, 3, 4):lazy():filter(function(v) return v % 2 == 0 end):count());
+        assert.are.same({ true, 4 }, { vector.of(1, 2, 3, 4):lazy():last() });
+    end);
+
+    it("Cannot be reused once consumed", function()
+        local lazy = vector.of(1, 2):lazy();
+        lazy:count();
+
+        assert.has.error_matches(function() lazy:count() end, "already consumed");
+    end);
+
+    it("Raises an error if the Vector is modified during evaluation", function()
+        local vec = vector.of(1, 2, 3);
+        local lazy = vec:lazy():map(function(value) vec:push(value); return value end);
+
+        assert.has.error_matches(function() lazy:collect() end, "modified during iteration");
+    end);
+
+    it("Rejects invalid counts and steps", function()
+        assert.has.error_matches(function() vector.of(1):lazy():take(-1) end, "invalid count");
+        assert.has.error_matches(function() vector.of(1):lazy():step_by(0) end, "invalid step 0");
+    end);
+end);
+
+describe("Windowed and grouped traversal", function()
+    local function tables(vec)
+        local result = {};
+        for i, inner in vec:iter() do result[i] = inner:to_table() end;
+        return result;
+    end;
+
+    it("Splits into chunks", function()
+        assert.are.same({ { 1, 2 }, { 3, 4 }, { 5 } }, tables(vector.of(1, 2, 3, 4, 5):chunks(2)));
+        assert.are.equals(0, #vector.new():chunks(3));
+    end);
+
+    it("Returns overlapping windows", function()
+        assert.are.same({ { 1, 2, 3 }, { 2, 3, 4 } }, tables(vector.of(1, 2, 3, 4):windows(3)));
+        assert.are.equals(0, #vector.of(1, 2):windows(3));
+    end);
+
+    it("Rejects non-positive sizes", function()
+        assert.has.error_matches(function() vector.of(1):chunks(0) end, "Invalid chunk size 0");
+        assert.has.error_matches(function() vector.of(1):windows(-1) end, "Invalid window size");
+    end);
+
+    it("Groups elements by key", function()
+        local groups = vector.of(1, 2, 3, 4, 5):group_by(function(value)
+            return value % 2 == 0 and "even" or "odd";
+        end);
+
+        assert.are.same({ 1, 3, 5 }, groups.odd:to_table());
+        assert.are.same({ 2, 4 }, groups.even:to_table());
+    end);
+
+    it("Rejects nil group keys", function()
+        assert.has.error_matches(function()
+            vector.of(1, 2):group_by(function(value) if value == 1 then return "a" end end);
+        end, "key at index 2 is nil");
+    end);
+
+    it("Partitions by a predicate", function()
+        local even, odd = vector.of(1, 2, 3, 4):partition(function(value) return value % 2 == 0 end);
+
+        assert.are.same({ 2, 4 }, even:to_table());
+        assert.are.same({ 1, 3 }, odd:to_table());
+    end);
+
+    it("Zips and unzips", function()
+        local zipped = vector.of(1, 2, 3):zip({ "a", "b" });
+        local numbers, letters = zipped:unzip();
+
+        assert.are.same({ { 1, "a" }, { 2, "b" } }, tables(zipped));
+        assert.are.same({ 1, 2 }, numbers:to_table());
+        assert.are.same({ "a", "b" }, letters:to_table());
+    end);
+
+    it("Rejects elements that are not pairs when unzipping", function()
+        assert.has.error_matches(function() vector.of({ 1, 2 }, 3):unzip() end, "Element at index 2 is a");
+        assert.has.error_matches(function() vector.of({ 1 }):unzip() end, "index 1 holds 1 values");
+    end);
+end);
+
+describe("Computed constructors", function()
+    it("Creates inclusive ranges", function()
+        assert.are.same({ 1, 2, 3, 4 }, vector.range(1, 4):to_table());
+        assert.are.same({ 10, 7, 4, 1 }, vector.range(10, 0, -3):to_table());
+        assert.are.equals(0, #vector.range(5, 1));
+        if math.type then
+            assert.are.equals("integer", math.type(vector.range(1, 2)[1]));
+        end;
+    end);
+
+    it("Creates integer ranges at the integer limits", function()
+        if not math.maxinteger then return end;
+        local min, max = math.mininteger, math.maxinteger;
+
+        assert.are.same({ min, -1, max - 1 }, vector.range(min, max, max):to_table());
+        assert.are.same({ max, -1 }, vector.range(max, min, min):to_table());
+    end);
+
+    it("Counts float ranges precisely", function()
+        local vec = vector.range(0, 1, 0.1);
+
+        assert.are.equals(11, #vec);
+        assert.are.equals(1, vec[-1]);
+        assert.are.equals(4, #vector.range(0, 0.3, 0.1));
+        assert.are.same({ 1, 0.5, 0 }, vector.range(1, 0, -0.5):to_table());
+    end);
+
+    it("Only yields elements on the progression", function()
+        local vec = vector.range(0, 1000.9999995, 1.0);
+
+        assert.are.equals(1001, #vec);
+        assert.are.equals(1000, vec[-1]);
+    end);
+
+    it("Rejects invalid ranges", function()
+        assert.has.error_matches(function() vector.range(1, 2, 0) end, "Invalid range step 0");
+        assert.has.error_matches(function() vector.range("a", 2) end, "Invalid range start");
+        assert.has.error_matches(function() vector.range(0, 2 ^ 62, 1e-9) end, "exceeds the maximum");
+    end);
+
+    it("Repeats a value", function()
+        assert.are.same({ "x", "x", "x" }, vector.rep("x", 3):to_table());
+        assert.has.error_matches(function() vector.rep("x", -1) end, "Invalid Vector len");
+    end);
+
+    it("Generates elements from their index", function()
+        local vec = vector.generate(4, function(i) return i * i end);
+
+        assert.are.same({ 1, 4, 9, 16 }, vec:to_table());
+        assert.has.error_matches(function() vector.generate(-2, tostring) end, "Invalid Vector len");
+        assert.has.error_matches(function() vector.generate(2 ^ 40, tostring) end, "Cannot allocate memory");
+        assert.has.errors(function() vector.generate(2 ^ 62, tostring) end);
+    end);
+
+    it("Collects a generic for iterator", function()
+        local words = vector.from_iter(string.gmatch("one two three", "%a+"));
+        local keys = vector.from_iter(ipairs({ "a", "b" }));
+
+        assert.are.same({ "one", "two", "three" }, words:to_table());
+        assert.are.same({ 1, 2 }, keys:to_table());
+    end);
+end);
+
+describe("Grids", function()
+    local function rows_of(grid)
+        local result = {};
+        for r = 1, grid:rows() do result[r] = grid:row(r):to_table() end;
+        return result;
+    end;
+
+    it("Gets and sets cells with presence flags", function()
+        local grid = vector.grid(2, 3, 0);
+
+        assert.is_true(grid:set(-1, -1, 9));
+        assert.is_false(grid:set(3, 1, 9));
+        assert.are.same({ true, 9 }, { grid:get(2, 3) });
+        assert.are.same({ false, nil }, { grid:get(1, 4) });
+        assert.are.equals(2, grid:rows());
+        assert.are.equals(3, grid:cols());
+    end);
+
+    it("Rejects negative dimensions", function()
+        assert.has.error_matches(function() vector.grid(-1, 2) end, "Invalid grid size");
+    end);
+
+    it("Extracts rows and columns as Vectors", function()
+        local grid = vector.grid(2, 2, 0);
+        grid:set(1, 2, 5);
+
+        assert.are.same({ 0, 5 }, grid:row(1):to_table());
+        assert.are.same({ 5, 0 }, grid:column(-1):to_table());
+        assert.has.error_matches(function() grid:row(3) end, "Row out of bounds 3");
+    end);
+
+    it("Transposes", function()
+        local grid = vector.grid(2, 3, 0);
+        grid:set(1, 3, 7);
+
+        assert.are.same({ { 0, 0 }, { 0, 0 }, { 7, 0 } }, rows_of(grid:transpose()));
+    end);
+
+    it("Transposes empty grids without visiting their cells", function()
+        local transposed = vector.grid(0, 2 ^ 40, 0):transpose();
+
+        assert.are.equals(2 ^ 40, transposed:rows());
+        assert.are.equals(0, transposed:cols());
+    end);
+
+    it("Flood fills a connected region", function()
+        local grid = vector.grid(3, 3, ".");
+        grid:set(2, 1, "#");
+        grid:set(2, 2, "#");
+        grid:set(1, 2, "#");
+
+        assert.are.equals(1, grid:flood_fill(1, 1, "o"));
+        assert.are.equals(5, grid:flood_fill(3, 3, "o"));
+        assert.are.same({ { "o", "#", "o" }, { "#", "#", "o" }, { "o", "o", "o" } }, rows_of(grid));
+    end);
+
+    it("Iterates over neighbours", function()
+        local grid = vector.grid(3, 3, 0);
+        local orthogonal, all = 0, 0;
+        for _ in grid:neighbours(1, 1) do orthogonal = orthogonal + 1 end;
+        for _ in grid:neighbours(2, 2, true) do all = all + 1 end;
+
+        local r, c = grid:neighbours(1, 1)();
+        assert.are.equals(2, orthogonal);
+        assert.are.equals(8, all);
+        assert.are.same({ 1, 2 }, { r, c });
+    end);
+
+    it("Multiplies matrices", function()
+        local a = vector.grid(2, 2, 0);
+        a:set(1, 1, 1); a:set(1, 2, 2); a:set(2, 1, 3); a:set(2, 2, 4);
+
+        assert.are.same({ { 7, 10 }, { 15, 22 } }, rows_of(a:matmul(a)));
+        assert.are.same(rows_of(a), rows_of(a:matmul(vector.identity(2))));
+        assert.has.error_matches(function() a:matmul(vector.grid(3, 1, 0)) end, "2x2 grid by a 3x1 grid");
+        assert.has.error_matches(function() a:matmul(vector.grid(2, 1, "x")) end, "is a string");
+        assert.has.error_matches(function() vector.grid(2 ^ 31, 0):matmul(vector.grid(0, 2 ^ 33)) end, "Invalid grid size");
+        assert.has.error_matches(function() vector.grid(2 ^ 31, 0):matmul(vector.grid(0, 2 ^ 31)) end, "Cannot allocate memory");
+    end);
+end);
+
+describe("Byte buffers", function()
+    it("Creates buffers from a length or a string", function()
+        local zeros = vector.bytes(3);
+        local text = vector.bytes("Hi!");
+
+        assert.are.equals(3, #zeros);
+        assert.are.equals(0, zeros[2]);
+        assert.are.equals(72, text[1]);
+        assert.are.equals("Hi!", text:to_string());
+    end);
+
+    it("Rejects lengths that cannot be allocated", function()
+        assert.has.error_matches(function() vector.bytes(2 ^ 62) end, "Cannot allocate memory");
+        assert.has.error_matches(function() vector.bytes(1e300) end, "Invalid Vector len");
+        assert.has.error_matches(function() vector.bytes(2.5) end, "Invalid Vector len");
+    end);
+
+    it("Gets and sets bytes like a Vector", function()
+        local bytes = vector.bytes(2);
+
+        assert.is_true(bytes:set(-1, 255));
+        assert.is_false(bytes:set(3, 1));
+        assert.are.same({ true, 255 }, { bytes:get(2) });
+        assert.are.same({ false, nil }, { bytes:get(0) });
+        assert.has.error_matches(function() bytes[1] = 256 end, "no exact u8 representation");
+        assert.has.error_matches(function() bytes:push("a") end, "index 3 is a string");
+    end);
+
+    it("Encodes and decodes hex and base64", function()
+        local bytes = vector.bytes("\0\255hello");
+
+        assert.are.equals("00ff68656c6c6f", bytes:to_hex());
+        assert.are.equals("AP9oZWxsbw==", bytes:to_base64());
+        assert.are.equals(bytes, vector.bytes_from_hex("00FF68656C6C6F"));
+        assert.are.equals(bytes, vector.bytes_from_base64("AP9oZWxsbw=="));
+        assert.has.error_matches(function() vector.bytes_from_hex("abc") end, "Invalid hex string");
+        assert.has.error_matches(function() vector.bytes_from_base64("!!") end, "Invalid base64 string");
+    end);
+
+    it("Reads and writes numbers in both byte orders", function()
+        local bytes = vector.bytes(12);
+        bytes:write_u16le(1, 0x1234);
+        bytes:write_i32be(3, -2);
+        bytes:write_f32le(-4, 1.5);
+
+        assert.are.equals("3412fffffffe", bytes:to_hex():sub(1, 12));
+        assert.are.equals(0x1234, bytes:read_u16le(1));
+        assert.are.equals(0x3412, bytes:read_u16be(1));
+        assert.are.equals(-2, bytes:read_i32be(3));
+        assert.are.equals(0xfe, bytes:read_u8(6));
+        assert.are.equals(-2, bytes:read_i8(6));
+        assert.are.equals(1.5, bytes:read_f32le(9));
+    end);
+
+    it("Round-trips doubles", function()
+        local bytes = vector.bytes(8);
+        bytes:write_f64be(1, math.pi);
+
+        assert.are.equals(math.pi, bytes:read_f64be(1));
+        assert.are.equals("400921fb54442d18", bytes:to_hex());
+    end);
+
+    it("Rounds floats to single precision like string.pack", function()
+        local bytes = vector.bytes(8);
+        bytes:write_f32le(1, 0.1);
+        bytes:write_f32be(5, 1e300);
+
+        assert.are.equals("cdcccc3d7f800000", bytes:to_hex());
+        assert.are.equals(vector.f32_from({ 0.1 })[1], bytes:read_f32le(1));
+        assert.are.equals(math.huge, bytes:read_f32be(5));
+        assert.has.error_matches(function() bytes:write_f32le(1, "x") end, "is a string");
+    end);
+
+    it("Rejects out-of-range reads and writes", function()
+        local bytes = vector.bytes(4);
+
+        assert.has.error_matches(function() bytes:read_u32le(2) end, "Cannot read 4 bytes at offset 2");
+        assert.has.error_matches(function() bytes:write_u16be(5, 1) end, "Cannot write 2 bytes at offset 5");
+        assert.has.error_matches(function() bytes:write_u16le(1, 70000) end, "no exact u16 representation");
+        assert.has.error_matches(function() bytes:write_i64le(1, 2 ^ 63) end, "no exact i64 representation");
+        assert.are.equals("00000000", bytes:to_hex());
+    end);
+end);
+
+
+describe("Change observers", function()
+    local function recorder()
+        local events = {};
+        return events, function(op, index, old, new)
+            events[#events + 1] = { op, index, old, new };
+        end;
+    end;
+
+    it("Reports element changes with their old and new values", function()
+        local vec = vector.of("a", "b");
+        local events, record = recorder();
+        vec:on_change(record);
+
+        vec:set(1, "x");
+        vec:push("c");
+        vec[-1] = "d";
+        vec:pop();
+
+        assert.are.same({
+            { "set", 1, "a", "x" },
+            { "push", 3, nil, "c" },
+            { "__newindex", 3, "c", "d" },
+            { "pop", 3, "d", nil },
+        }, events);
+    end);
+
+    it("Reports bulk changes without an index", function()
+        local vec = vector.of(3, 1, 2);
+        local events, record = recorder();
+        vec:on_change(record);
+
+        vec:sort();
+        vec:extend({ 4 });
+
+        assert.are.same({ { "sort" }, { "extend" } }, events);
+    end);
+
+    it("Reports every position touched by a swap", function()
+        local vec = vector.of("a", "b", "c");
+        local events, record = recorder();
+        vec:on_change(record);
+
+        vec:swap(1, 2);
+        vec:swap_remove(1);
+        vec:swap_remove(-1);
+
+        assert.are.same({
+            { "swap", 1, "a", "b" },
+            { "swap", 2, "b", "a" },
+            { "swap_remove", 1, "b", "c" },
+            { "swap_remove", 3, "c", nil },
+            { "swap_remove", 2, "a", nil },
+        }, events);
+    end);
+
+    it("Does not report operations that change nothing", function()
+        local vec = vector.new();
+        local events, record = recorder();
+        vec:on_change(record);
+
+        vec:pop();
+        vec:set(1, "x");
+        vec:clear();
+
+        assert.are.same({}, events);
+    end);
+
+    it("Stops reporting once unsubscribed", function()
+        local vec = vector.new();
+        local events, record = recorder();
+        local subscription = vec:on_change(record);
+
+        vec:push(1);
+        assert.is_true(subscription:unsubscribe());
+        vec:push(2);
+
+        assert.are.equals(1, #events);
+        assert.is_false(subscription:is_active());
+        assert.is_false(subscription:unsubscribe());
+    end);
+
+    it("Lets observers read the Vector", function()
+        local vec = vector.new();
+        local seen;
+        vec:on_change(function() seen = #vec end);
+
+        vec:push("a");
+
+        assert.are.equals(1, seen);
+    end);
+
+    it("Coalesces the notifications of a batch", function()
+        local vec = vector.new();
+        local events, record = recorder();
+        vec:on_change(record);
+
+        local result = vec:batch(function(v)
+            v:push(1);
+            v:batch(function() v:push(2) end);
+            return "done";
+        end);
+
+        assert.are.equals("done", result);
+        assert.are.same({ { "batch" } }, events);
+        assert.are.equals(2, #vec);
+    end);
+
+    it("Does not notify for a batch that changes nothing", function()
+        local vec = vector.new();
+        local events, record = recorder();
+        vec:on_change(record);
+
+        vec:batch(function() end);
+
+        assert.are.same({}, events);
+    end);
+
+    it("Reports observer errors without corrupting the Vector", function()
+        local vec = vector.of(1);
+        local calls = 0;
+        vec:on_change(function() error("boom") end);
+        vec:on_change(function() calls = calls + 1 end);
+
+        assert.has.error_matches(function() vec:push(2) end, "observer failed after push");
+        assert.are.same({ 1, 2 }, vec:to_table());
+        assert.are.equals(1, calls);
+
+        assert.has.errors(function() vec:push(3) end);
+        assert.are.equals(3, #vec);
+        assert.are.equals(2, calls);
+    end);
+
+    it("Reports changes made through views", function()
+        local vec = vector.of(1, 2, 3);
+        local events, record = recorder();
+        vec:on_change(record);
+
+        vec:view(2, 3)[1] = 20;
+
+        assert.are.same({ { "__newindex", 2, 2, 20 } }, events);
+    end);
+
+    it("Does not carry observers over to copies", function()
+        local vec = vector.of(1);
+        local events, record = recorder();
+        vec:on_change(record);
+
+        local copy = vec:thaw_copy();
+        copy:push(2);
+
+        assert.are.same({}, events);
+    end);
+
+    it("Does not keep alive a Vector captured by its observer", function()
+        local tracked = setmetatable({}, { __mode = "k" });
+        local vec = vector.of(1);
+        local subscription = vec:on_change(function() return #vec end);
+        tracked[vec] = true;
+        tracked[subscription] = true;
+
+        vec, subscription = nil, nil;
+        collectgarbage();
+        collectgarbage();
+
+        assert.is_nil(next(tracked));
+    end);
+end);
+
+describe("Undo history", function()
+    it("Undoes and redoes element changes", function()
+        local vec = vector.of("a", "b");
+        vec:track_history();
+
+        vec:set(1, "x");
+        vec:push("c");
+        vec:insert(1, "first");
+        vec:remove(2);
+        assert.are.same({ "first", "b", "c" }, vec:to_table());
+
+        for _ = 1, 4 do assert.is_true(vec:undo()) end
+        assert.are.same({ "a", "b" }, vec:to_table());
+        assert.is_false(vec:undo());
+
+        for _ = 1, 4 do assert.is_true(vec:redo()) end
+        assert.are.same({ "first", "b", "c" }, vec:to_table());
+        assert.is_false(vec:redo());
+    end);
+
+    it("Undoes bulk changes", function()
+        local vec = vector.of(3, 1, 2);
+        vec:track_history();
+
+        vec:sort();
+        vec:extend({ 4, 5 });
+        vec:truncate(2);
+        vec:clear();
+
+        vec:undo();
+        assert.are.same({ 1, 2 }, vec:to_table());
+        vec:undo();
+        assert.are.same({ 1, 2, 3, 4, 5 }, vec:to_table());
+        vec:undo();
+        vec:undo();
+        assert.are.same({ 3, 1, 2 }, vec:to_table());
+
+        vec:redo();
+        assert.are.same({ 1, 2, 3 }, vec:to_table());
+        vec:redo();
+        vec:redo();
+        assert.are.same({ 1, 2 }, vec:to_table());
+        vec:redo();
+        assert.are.same({}, vec:to_table());
+    end);
+
+    it("Undoes swaps element by element", function()
+        local vec = vector.of("a", "b", "c");
+        vec:track_history();
+
+        vec:swap(1, 3);
+        vec:swap_remove(1);
+        vec:swap_remove(-1);
+
+        vec:undo();
+        assert.are.same({ "a", "b" }, vec:to_table());
+        vec:undo();
+        assert.are.same({ "c", "b", "a" }, vec:to_table());
+        vec:undo();
+        assert.are.same({ "a", "b", "c" }, vec:to_table());
+
+        vec:redo();
+        vec:redo();
+        vec:redo();
+        assert.are.same({ "a" }, vec:to_table());
+    end);
+
+    it("Does not record changes before tracking starts", function()
+        local vec = vector.new();
+        vec:push(1);
+
+        assert.is_false(vec:undo());
+
+        vec:track_history();
+        vec:push(2);
+        vec:undo();
+        assert.are.same({ 1 }, vec:to_table());
+    end);
+
+    it("Discards undone steps after a new change", function()
+        local vec = vector.new();
+        vec:track_history();
+
+        vec:push(1);
+        vec:push(2);
+        vec:undo();
+        vec:push(3);
+
+        assert.is_false(vec:redo());
+        assert.are.same({ 1, 3 }, vec:to_table());
+    end);
+
+    it("Keeps at most the given number of steps", function()
+        local vec = vector.new();
+        vec:track_history(2);
+
+        vec:push(1);
+        vec:push(2);
+        vec:push(3);
+
+        assert.is_true(vec:undo());
+        assert.is_true(vec:undo());
+        assert.is_false(vec:undo());
+        assert.are.same({ 1 }, vec:to_table());
+    end);
+
+    it("Shrinks the history when the limit is lowered", function()
+        local vec = vector.new();
+        vec:track_history();
+        vec:push(1);
+        vec:push(2);
+
+        vec:track_history(1);
+
+        assert.is_true(vec:undo());
+        assert.is_false(vec:undo());
+    end);
+
+    it("Rejects invalid limits", function()
+        assert.has.error_matches(function() vector.new():track_history(0) end, "Invalid history limit 0");
+    end);
+
+    it("Groups the changes of a batch into one step", function()
+        local vec = vector.of(1);
+        vec:track_history();
+
+        vec:batch(function(v)
+            v:push(2);
+            v:batch(function() v:push(3) end);
+            v[1] = 10;
+        end);
+        vec:push(4);
+
+        vec:undo();
+        assert.are.same({ 10, 2, 3 }, vec:to_table());
+        vec:undo();
+        assert.are.same({ 1 }, vec:to_table());
+        vec:redo();
+        assert.are.same({ 10, 2, 3 }, vec:to_table());
+    end);
+
+    it("Undoes a batch mixing bulk and element changes", function()
+        local vec = vector.of(3, 1, 2);
+        vec:track_history();
+
+        vec:batch(function(v)
+            v:push(4);
+            v:sort();
+            v[1] = 10;
+            v:reverse();
+            v:truncate(2);
+            v:pop();
+        end);
+
+        vec:undo();
+        assert.are.same({ 3, 1, 2 }, vec:to_table());
+        vec:redo();
+        assert.are.same({ 4 }, vec:to_table());
+    end);
+
+    it("Refuses to undo inside a batch", function()
+        local vec = vector.new();
+        vec:track_history();
+        vec:push(1);
+
+        vec:batch(function(v)
+            assert.has.error_matches(function() v:undo() end, "Cannot undo inside a batch");
+            assert.has.error_matches(function() v:redo() end, "Cannot redo inside a batch");
+        end);
+        assert.is_true(vec:undo());
+    end);
+
+    it("Records changes made through views", function()
+        local vec = vector.of(1, 2, 3);
+        vec:track_history();
+
+        vec:view(2, 3)[1] = 20;
+        vec:undo();
+
+        assert.are.same({ 1, 2, 3 }, vec:to_table());
+    end);
+
+    it("Notifies observers of undone changes", function()
+        local vec = vector.of("a");
+        vec:track_history();
+        vec:set(1, "b");
+        local events = {};
+        vec:on_change(function(op, index, old, new) events[#events + 1] = { op, index, old, new } end);
+
+        vec:undo();
+
+        assert.are.same({ { "undo", 1, "b", "a" } }, events);
+    end);
+
+    it("Rejects undo on frozen vectors", function()
+        local vec = vector.new();
+        vec:track_history();
+        vec:push(1);
+        vec:freeze();
+
+        assert.has.error_matches(function() vec:undo() end, "frozen");
+    end);
+
+    it("Does not carry the history over to copies", function()
+        local vec = vector.new();
+        vec:track_history();
+        vec:push(1);
+
+        assert.is_false(vec:thaw_copy():undo());
+    end);
+
+    it("Restores snapshots", function()
+        local vec = vector.of(1, 2, 3);
+        local snapshot = vec:snapshot();
+        vec:track_history();
+
+        vec:reverse();
+        vec:push(4);
+        vec:restore(snapshot);
+
+        assert.are.same({ 1, 2, 3 }, vec:to_table());
+        assert.are.equals(3, #snapshot);
+        assert.are.same({ 1, 2, 3 }, snapshot:to_vector():to_table());
+
+        vec:undo();
+        assert.are.same({ 3, 2, 1, 4 }, vec:to_table());
+    end);
+
+    it("Keeps snapshots independent from the Vector", function()
+        local vec = vector.of(1);
+        local snapshot = vec:snapshot();
+
+        vec:push(2);
+        local copy = snapshot:to_vector();
+        copy:push(3);
+
+        assert.are.equals(1, #snapshot);
+        assert.are.same({ 1, 2 }, vec:to_table());
+    end);
+end);
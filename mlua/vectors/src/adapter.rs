@@ -1,5 +1,10 @@
 use mlua::prelude::*;
 
+use crate::compare;
+use crate::iter::{Direction, LuaVectorCursor};
+use crate::linalg::{ArithOp, Side};
+use crate::observe_adapter;
+use crate::serial;
 use crate::vector::LuaVector;
 
 /// A type alias for the `Vector` struct, used for clarity in Lua bindings.
@@ -11,7 +16,7 @@ pub(super) type LuaVectorAdapter = Vector;
 /// conversions and error handling, abstracting the core logic away from the FFI.
 pub(super) struct Vector {
     /// The internal `LuaVector` that holds the actual data.
-    vec: LuaVector,
+    pub(super) vec: LuaVector,
 }
 
 impl LuaVectorAdapter {
@@ -32,6 +37,30 @@ impl LuaVectorAdapter {
         }
     }
 
+    /// The Lua-facing constructor for creating a `Vector` from a table.
+    ///
+    /// The elements from `1` to `n` are copied, so holes (`nil` values) are kept when an
+    /// explicit length is given. Without `n`, the raw length of the table is used.
+    /// Causes a Lua error if `n` is negative.
+    pub(super) fn from_table(
+        _: &Lua,
+        (table, n): (LuaTable, Option<LuaInteger>),
+    ) -> LuaResult<Self> {
+        let len = n.unwrap_or(table.raw_len() as LuaInteger);
+        if len < 0 {
+            let msg = format!("Invalid Vector len {}.", len);
+            return Err(LuaError::RuntimeError(msg));
+        }
+
+        let values = (1..=len)
+            .map(|idx| table.raw_get(idx))
+            .collect::<LuaResult<_>>()?;
+
+        Ok(Self {
+            vec: LuaVector::of(values),
+        })
+    }
+
     /// The Lua-facing constructor for creating a `Vector` from variadic arguments.
     ///
     /// It accepts any number of arguments and uses them to populate a new vector.
@@ -41,6 +70,74 @@ impl LuaVectorAdapter {
         })
     }
 
+    /// The Lua-facing `vector.range` constructor.
+    ///
+    /// Creates the inclusive progression from `start` to `stop`, with a `step` of `1` by
+    /// default. Integer arguments produce integers, and float steps produce exactly the
+    /// elements that fit in the bounds. Causes a Lua error if `step` is zero or the range
+    /// is too long.
+    pub(super) fn range(
+        _: &Lua,
+        (start, stop, step): (LuaValue, LuaValue, Option<LuaValue>),
+    ) -> LuaResult<Self> {
+        let step = step.unwrap_or(LuaValue::Integer(1));
+        let vec = LuaVector::range(&start, &stop, &step).map_err(LuaError::RuntimeError)?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `vector.rep` constructor.
+    ///
+    /// Creates a `Vector` holding `n` copies of `value`. Causes a Lua error if `n` is
+    /// negative.
+    pub(super) fn rep(_: &Lua, (value, n): (LuaValue, LuaInteger)) -> LuaResult<Self> {
+        let vec = LuaVector::new(n, value).map_err(LuaError::RuntimeError)?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `vector.generate` constructor.
+    ///
+    /// Creates a `Vector` of `n` elements, where the element at index `i` is `func(i)`.
+    /// Causes a Lua error if `n` is negative or too large to be allocated.
+    pub(super) fn generate(_: &Lua, (n, func): (LuaInteger, LuaFunction)) -> LuaResult<Self> {
+        let vec = LuaVector::generate(n, |index| {
+            func.call(index)
+                .map_err(|err| callback_error("generate", index, err))
+        })?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `vector.from_iter` constructor.
+    ///
+    /// Consumes a generic `for` iterator, given as the usual iterator function, state and
+    /// initial control value, collecting the first value of every step until it is `nil`.
+    pub(super) fn from_iter(
+        _: &Lua,
+        (iter, state, mut control): (LuaFunction, LuaValue, LuaValue),
+    ) -> LuaResult<Self> {
+        let mut vec = LuaVector::of(Vec::new());
+
+        loop {
+            let index = vec.len() + 1;
+            control = iter
+                .call::<LuaMultiValue>((state.clone(), control))
+                .map_err(|err| callback_error("from_iter", index, err))?
+                .into_iter()
+                .next()
+                .unwrap_or(LuaNil);
+
+            if control.is_nil() {
+                return Ok(Self { vec });
+            }
+            if !vec.push(control.clone()) {
+                let msg = "from_iter: the iterator exceeded the maximum Vector len.".to_string();
+                return Err(LuaError::RuntimeError(msg));
+            }
+        }
+    }
+
     /// The Lua-facing `get` method.
     ///
     /// It returns a pair of values: a boolean presence flag and the value itself.
@@ -170,4 +267,986 @@ impl LuaVectorAdapter {
             Ok(())
         }
     }
-}
\ No newline at end of file
+
+    /// The Lua-facing `map` method.
+    ///
+    /// Calls `func(value, index)` for every element and returns a new `Vector`
+    /// with the results. Errors raised by the callback are reported along with
+    /// the index of the element being processed.
+    pub(super) fn map(_: &Lua, this: &Self, (func,): (LuaFunction,)) -> LuaResult<Self> {
+        let vec = this.vec.map(|index, value| {
+            func.call((value.clone(), index))
+                .map_err(|err| callback_error("map", index, err))
+        })?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `filter` method.
+    ///
+    /// Returns a new `Vector` with the elements for which `pred(value, index)`
+    /// returned a truthy value.
+    pub(super) fn filter(_: &Lua, this: &Self, (pred,): (LuaFunction,)) -> LuaResult<Self> {
+        let vec = this
+            .vec
+            .filter(|index, value| call_predicate("filter", &pred, index, value))?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `reduce` method.
+    ///
+    /// Calls `func(acc, value, index)` for every element, threading the accumulator.
+    /// When `init` is omitted the first element is used as the initial accumulator,
+    /// and an empty vector reduces to `nil`. An explicit `nil` is a valid `init`.
+    pub(super) fn reduce(
+        _: &Lua,
+        this: &Self,
+        (func, init): (LuaFunction, LuaMultiValue),
+    ) -> LuaResult<LuaValue> {
+        let init = init.into_iter().next();
+
+        let result = this.vec.fold(init, |acc, index, value| {
+            func.call((acc, value.clone(), index))
+                .map_err(|err| callback_error("reduce", index, err))
+        })?;
+
+        Ok(result.unwrap_or(LuaNil))
+    }
+
+    /// The Lua-facing `find` method.
+    ///
+    /// It returns a presence flag, the first element accepted by `pred(value, index)`
+    /// and its index. When no element matches it returns `false, nil, nil`.
+    pub(super) fn find(
+        _: &Lua,
+        this: &Self,
+        (pred,): (LuaFunction,),
+    ) -> LuaResult<(LuaValue, LuaValue, LuaValue)> {
+        let position = this
+            .vec
+            .position(|index, value| call_predicate("find", &pred, index, value))?;
+
+        match position.and_then(|index| Some((index, this.vec.get(index)?))) {
+            Some((index, value)) => Ok((LuaValue::Boolean(true), value, LuaValue::Integer(index))),
+            None => Ok((LuaValue::Boolean(false), LuaNil, LuaNil)),
+        }
+    }
+
+    /// The Lua-facing `any` method.
+    ///
+    /// Returns `true` if `pred(value, index)` is truthy for at least one element.
+    pub(super) fn any(_: &Lua, this: &Self, (pred,): (LuaFunction,)) -> LuaResult<bool> {
+        this.vec
+            .any(|index, value| call_predicate("any", &pred, index, value))
+    }
+
+    /// The Lua-facing `all` method.
+    ///
+    /// Returns `true` if `pred(value, index)` is truthy for every element.
+    pub(super) fn all(_: &Lua, this: &Self, (pred,): (LuaFunction,)) -> LuaResult<bool> {
+        this.vec
+            .all(|index, value| call_predicate("all", &pred, index, value))
+    }
+
+    /// The Lua-facing `for_each` method.
+    ///
+    /// Calls `func(value, index)` for every element, discarding the results.
+    pub(super) fn for_each(_: &Lua, this: &Self, (func,): (LuaFunction,)) -> LuaResult<()> {
+        this.vec.for_each(|index, value| {
+            func.call((value.clone(), index))
+                .map_err(|err| callback_error("for_each", index, err))
+        })
+    }
+
+    /// The Lua-facing `contains` method.
+    ///
+    /// Returns `true` if an element is equal to `value`, using the same equality as `__eq`.
+    pub(super) fn contains(_: &Lua, this: &Self, (value,): (LuaValue,)) -> LuaResult<bool> {
+        Ok(this.vec.contains(&value))
+    }
+
+    /// The Lua-facing `index_of` method.
+    ///
+    /// Returns the index of the first element equal to `value`, searching from `start`
+    /// (defaulting to `1`), or `nil` if there is none.
+    pub(super) fn index_of(
+        _: &Lua,
+        this: &Self,
+        (value, start): (LuaValue, Option<LuaInteger>),
+    ) -> LuaResult<Option<LuaInteger>> {
+        Ok(this.vec.index_of(&value, start.unwrap_or(1)))
+    }
+
+    /// The Lua-facing `last_index_of` method.
+    ///
+    /// Returns the index of the last element equal to `value`, or `nil` if there is none.
+    pub(super) fn last_index_of(
+        _: &Lua,
+        this: &Self,
+        (value,): (LuaValue,),
+    ) -> LuaResult<Option<LuaInteger>> {
+        Ok(this.vec.last_index_of(&value))
+    }
+
+    /// The Lua-facing `count` method.
+    ///
+    /// Given a function, counts the elements for which `pred(value, index)` is truthy.
+    /// Given any other value, counts the elements equal to it.
+    pub(super) fn count(_: &Lua, this: &Self, (target,): (LuaValue,)) -> LuaResult<LuaInteger> {
+        match target {
+            LuaValue::Function(pred) => this
+                .vec
+                .count_by(|index, value| call_predicate("count", &pred, index, value)),
+            target => this
+                .vec
+                .count_by(|_, value| Ok::<_, LuaError>(*value == target)),
+        }
+    }
+
+    /// The Lua-facing `unique` method.
+    ///
+    /// Returns a new `Vector` keeping only the first occurrence of every element.
+    pub(super) fn unique(_: &Lua, this: &Self, _: ()) -> LuaResult<Self> {
+        Ok(Self {
+            vec: this.vec.unique(),
+        })
+    }
+
+    /// The Lua-facing `chunks` method.
+    ///
+    /// Returns a `Vector` of consecutive chunks of `size` elements, the last one possibly
+    /// shorter. Causes a Lua error if `size` is not positive.
+    pub(super) fn chunks(lua: &Lua, this: &Self, (size,): (LuaInteger,)) -> LuaResult<Self> {
+        let chunks = this.vec.chunks(size).map_err(LuaError::RuntimeError)?;
+
+        nested_vector(lua, chunks)
+    }
+
+    /// The Lua-facing `windows` method.
+    ///
+    /// Returns a `Vector` of every overlapping window of `size` elements. Causes a Lua
+    /// error if `size` is not positive.
+    pub(super) fn windows(lua: &Lua, this: &Self, (size,): (LuaInteger,)) -> LuaResult<Self> {
+        let windows = this.vec.windows(size).map_err(LuaError::RuntimeError)?;
+
+        nested_vector(lua, windows)
+    }
+
+    /// The Lua-facing `group_by` method.
+    ///
+    /// Calls `key_fn(value, index)` for every element and returns a table mapping each
+    /// key to a `Vector` of the elements that produced it. Keys that cannot index a
+    /// table (`nil` and NaN) cause a Lua error.
+    pub(super) fn group_by(
+        lua: &Lua,
+        this: &Self,
+        (key_fn,): (LuaFunction,),
+    ) -> LuaResult<LuaTable> {
+        let groups = this.vec.group_by(|index, value| {
+            let key = key_fn
+                .call::<LuaValue>((value.clone(), index))
+                .map_err(|err| callback_error("group_by", index, err))?;
+
+            match key {
+                LuaValue::Nil => Err(invalid_key(index, "nil")),
+                LuaValue::Number(num) if num.is_nan() => Err(invalid_key(index, "NaN")),
+                key => Ok(key),
+            }
+        })?;
+
+        let table = lua.create_table_with_capacity(0, groups.len())?;
+        for (key, vec) in groups {
+            table.raw_set(key, Self { vec })?;
+        }
+        Ok(table)
+    }
+
+    /// The Lua-facing `partition` method.
+    ///
+    /// Returns two new `Vector`s: the elements for which `pred(value, index)` is truthy,
+    /// and the remaining ones.
+    pub(super) fn partition(
+        _: &Lua,
+        this: &Self,
+        (pred,): (LuaFunction,),
+    ) -> LuaResult<(Self, Self)> {
+        let (accepted, rejected) = this
+            .vec
+            .partition(|index, value| call_predicate("partition", &pred, index, value))?;
+
+        Ok((Self { vec: accepted }, Self { vec: rejected }))
+    }
+
+    /// The Lua-facing `zip` method.
+    ///
+    /// Returns a `Vector` of two-element `Vector`s pairing the elements of this vector
+    /// with those of `other`, a `Vector` or a table. The result is as long as the shorter
+    /// of the two.
+    pub(super) fn zip(lua: &Lua, this: &Self, (other,): (LuaValue,)) -> LuaResult<Self> {
+        let other = LuaVector::of(values_of(&other)?);
+
+        nested_vector(lua, this.vec.zip(&other))
+    }
+
+    /// The Lua-facing `unzip` method.
+    ///
+    /// Splits a `Vector` of pairs, each a two-element `Vector` or table, into the
+    /// `Vector` of first elements and the `Vector` of second elements.
+    pub(super) fn unzip(_: &Lua, this: &Self, _: ()) -> LuaResult<(Self, Self)> {
+        let pairs = this
+            .vec
+            .as_slice()
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                values_of(value).map_err(|_| {
+                    let msg = format!(
+                        "Element at index {} is a {}, expected a pair.",
+                        idx + 1,
+                        value.type_name()
+                    );
+                    LuaError::runtime(msg)
+                })
+            })
+            .collect::<LuaResult<_>>()?;
+
+        let (firsts, seconds) = LuaVector::unzip(pairs).map_err(LuaError::RuntimeError)?;
+        Ok((Self { vec: firsts }, Self { vec: seconds }))
+    }
+
+    /// The Lua-facing `dedup` method.
+    ///
+    /// Removes consecutive repeated elements in place, keeping the first of each run.
+    pub(super) fn dedup(_: &Lua, this: &mut Self, _: ()) -> LuaResult<()> {
+        this.vec.dedup();
+        Ok(())
+    }
+
+    /// The Lua-facing `sort` method.
+    ///
+    /// Sorts the vector in place using `cmp(a, b)` as a "less than" comparator, or the
+    /// default ordering for numbers and strings when `cmp` is omitted. The sort is not
+    /// stable. The vector is left untouched if the comparison fails.
+    pub(super) fn sort(_: &Lua, this: &mut Self, (cmp,): (Option<LuaFunction>,)) -> LuaResult<()> {
+        let consistent = this.vec.sort_by(|a, b| less_than(&cmp, a, b))?;
+
+        ensure_consistent("sort", consistent)
+    }
+
+    /// The Lua-facing `sort_stable` method.
+    ///
+    /// Behaves like `sort`, but equal elements keep their relative order.
+    pub(super) fn sort_stable(
+        _: &Lua,
+        this: &mut Self,
+        (cmp,): (Option<LuaFunction>,),
+    ) -> LuaResult<()> {
+        let consistent = this.vec.sort_stable_by(|a, b| less_than(&cmp, a, b))?;
+
+        ensure_consistent("sort_stable", consistent)
+    }
+
+    /// The Lua-facing `sorted` method.
+    ///
+    /// Returns a stably sorted copy of the vector, leaving the original unchanged.
+    pub(super) fn sorted(_: &Lua, this: &Self, (cmp,): (Option<LuaFunction>,)) -> LuaResult<Self> {
+        let mut vec = this.vec.thawed_copy();
+        let consistent = vec.sort_stable_by(|a, b| less_than(&cmp, a, b))?;
+
+        ensure_consistent("sorted", consistent)?;
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `binary_search` method.
+    ///
+    /// Searches a sorted vector for `value`. It returns a presence flag and an index:
+    /// the index of a matching element when found, or the index where `value` could be
+    /// inserted to keep the vector sorted otherwise.
+    pub(super) fn binary_search(
+        _: &Lua,
+        this: &Self,
+        (value, cmp): (LuaValue, Option<LuaFunction>),
+    ) -> LuaResult<(LuaValue, LuaInteger)> {
+        let result = this
+            .vec
+            .binary_search_by(&value, |a, b| less_than(&cmp, a, b))?;
+
+        match result {
+            Ok(index) => Ok((LuaValue::Boolean(true), index)),
+            Err(index) => Ok((LuaValue::Boolean(false), index)),
+        }
+    }
+
+    /// The Lua-facing `partition_point` method.
+    ///
+    /// Returns the index of the first element for which `pred(value)` is falsy, assuming
+    /// every accepted element comes before every rejected one, or `#v + 1` if none is.
+    pub(super) fn partition_point(
+        _: &Lua,
+        this: &Self,
+        (pred,): (LuaFunction,),
+    ) -> LuaResult<LuaInteger> {
+        this.vec.partition_point(|value| {
+            pred.call::<LuaValue>(value.clone())
+                .map(|result| is_truthy(&result))
+        })
+    }
+
+    /// The Lua-facing `slice` method.
+    ///
+    /// Returns a new `Vector` with a copy of the elements between `i` and `j`, inclusive.
+    /// `i` defaults to `1` and `j` to `-1`; both are clamped to the bounds like `string.sub`.
+    pub(super) fn slice(
+        _: &Lua,
+        this: &Self,
+        (i, j): (Option<LuaInteger>, Option<LuaInteger>),
+    ) -> LuaResult<Self> {
+        Ok(Self {
+            vec: this.vec.slice(i.unwrap_or(1), j.unwrap_or(-1)),
+        })
+    }
+
+    /// The Lua-facing `insert` method.
+    ///
+    /// Inserts `value` so that it ends up at `index`, shifting the following elements.
+    /// Returns `true` on success and `false` if the index is out of bounds.
+    pub(super) fn insert(
+        _: &Lua,
+        this: &mut Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        Ok(LuaValue::Boolean(this.vec.insert(index, value)))
+    }
+
+    /// The Lua-facing `remove` method.
+    ///
+    /// Removes the element at `index`, shifting the following elements, and returns it
+    /// along with a presence flag.
+    pub(super) fn remove(
+        _: &Lua,
+        this: &mut Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        match this.vec.remove(index) {
+            Some(value) => Ok((LuaValue::Boolean(true), value)),
+            None => Ok((LuaValue::Boolean(false), LuaNil)),
+        }
+    }
+
+    /// The Lua-facing `swap_remove` method.
+    ///
+    /// Removes the element at `index`, replacing it with the last element, and returns
+    /// it along with a presence flag.
+    pub(super) fn swap_remove(
+        _: &Lua,
+        this: &mut Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        match this.vec.swap_remove(index) {
+            Some(value) => Ok((LuaValue::Boolean(true), value)),
+            None => Ok((LuaValue::Boolean(false), LuaNil)),
+        }
+    }
+
+    /// The Lua-facing `splice` method.
+    ///
+    /// Removes `count` elements starting at `index` and inserts the remaining arguments
+    /// in their place. It returns a success flag and a new `Vector` with the removed
+    /// elements, or `false, nil` if the index is out of bounds or `count` is negative.
+    pub(super) fn splice(
+        _: &Lua,
+        this: &mut Self,
+        (index, count, values): (LuaInteger, LuaInteger, LuaVariadic<LuaValue>),
+    ) -> LuaResult<(LuaValue, Option<Self>)> {
+        match this.vec.splice(index, count, values.to_vec()) {
+            Some(vec) => Ok((LuaValue::Boolean(true), Some(Self { vec }))),
+            None => Ok((LuaValue::Boolean(false), None)),
+        }
+    }
+
+    /// The Lua-facing `extend` method.
+    ///
+    /// Appends every element of another `Vector`, or of the sequence part of a table,
+    /// and returns `true` on success. It is registered as a function so that a vector
+    /// can be extended with itself.
+    pub(super) fn extend(
+        _: &Lua,
+        (this, other): (LuaAnyUserData, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        let values = values_of(&other)?;
+        let (extended, changes) = {
+            let mut vec = this.borrow_mut::<Self>()?;
+            vec.ensure_mutable("extend")?;
+            (vec.vec.extend(values), vec.vec.finish_mutation())
+        };
+
+        observe_adapter::notify(&this, "extend", changes)?;
+        Ok(LuaValue::Boolean(extended))
+    }
+
+    /// The Lua-facing `truncate` method.
+    ///
+    /// Keeps only the first `len` elements. Causes a Lua error if `len` is negative.
+    pub(super) fn truncate(_: &Lua, this: &mut Self, (len,): (LuaInteger,)) -> LuaResult<()> {
+        this.vec.truncate(len).map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `clear` method, which removes every element.
+    pub(super) fn clear(_: &Lua, this: &mut Self, _: ()) -> LuaResult<()> {
+        this.vec.clear();
+        Ok(())
+    }
+
+    /// The Lua-facing `__close` metamethod, available on Lua 5.4.
+    ///
+    /// It releases the spare capacity of a vector declared as `local v <close>` as soon as
+    /// it goes out of scope. The elements are kept, as other references may still use them.
+    #[cfg(feature = "lua54")]
+    pub(super) fn close(_: &Lua, this: &mut Self, _: LuaMultiValue) -> LuaResult<()> {
+        this.vec.shrink_to_fit();
+        Ok(())
+    }
+
+    /// The Lua-facing `vector.with_capacity` constructor.
+    ///
+    /// Creates an empty `Vector` able to hold `capacity` elements without reallocating.
+    /// Causes a Lua error if the capacity is negative or too large to allocate.
+    pub(super) fn with_capacity(_: &Lua, (capacity,): (LuaInteger,)) -> LuaResult<Self> {
+        LuaVector::with_capacity(capacity)
+            .map(|vec| Self { vec })
+            .map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `capacity` method.
+    ///
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub(super) fn capacity(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.vec.capacity())
+    }
+
+    /// The Lua-facing `reserve` method.
+    ///
+    /// Reserves capacity for at least `additional` more elements. Causes a Lua error if
+    /// `additional` is negative or the allocation is too large, leaving the vector unchanged.
+    pub(super) fn reserve(_: &Lua, this: &mut Self, (additional,): (LuaInteger,)) -> LuaResult<()> {
+        this.vec.reserve(additional).map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `shrink_to_fit` method.
+    ///
+    /// Releases the unused capacity of the vector.
+    pub(super) fn shrink_to_fit(_: &Lua, this: &mut Self, _: ()) -> LuaResult<()> {
+        this.vec.shrink_to_fit();
+        Ok(())
+    }
+
+    /// The Lua-facing `swap` method.
+    ///
+    /// Swaps the elements at `i` and `j`. Returns `true` on success and `false` if either
+    /// index is out of bounds.
+    pub(super) fn swap(
+        _: &Lua,
+        this: &mut Self,
+        (i, j): (LuaInteger, LuaInteger),
+    ) -> LuaResult<LuaValue> {
+        Ok(LuaValue::Boolean(this.vec.swap(i, j)))
+    }
+
+    /// The Lua-facing `reverse` method, which reverses the vector in place.
+    pub(super) fn reverse(_: &Lua, this: &mut Self, _: ()) -> LuaResult<()> {
+        this.vec.reverse();
+        Ok(())
+    }
+
+    /// The Lua-facing `rotate` method.
+    ///
+    /// Rotates the vector in place by `n` positions towards the end, or towards the
+    /// start if `n` is negative.
+    pub(super) fn rotate(_: &Lua, this: &mut Self, (n,): (LuaInteger,)) -> LuaResult<()> {
+        this.vec.rotate(n);
+        Ok(())
+    }
+
+    /// The Lua-facing `iter` method.
+    ///
+    /// Returns an iterator function for a generic `for`, yielding each index and value
+    /// from first to last. The iterator raises an error if the vector changes length
+    /// while being traversed.
+    pub(super) fn iter(lua: &Lua, this: LuaAnyUserData) -> LuaResult<LuaFunction> {
+        iterator(lua, this, Direction::Forward, None)
+    }
+
+    /// The Lua-facing `iter_rev` method.
+    ///
+    /// Like `iter`, but yields the elements from last to first, with their original indices.
+    pub(super) fn iter_rev(lua: &Lua, this: LuaAnyUserData) -> LuaResult<LuaFunction> {
+        iterator(lua, this, Direction::Backward, None)
+    }
+
+    /// The Lua-facing `enumerate` method.
+    ///
+    /// Like `iter`, but yields a counter starting at `start` (defaults to `1`) instead
+    /// of the element index.
+    pub(super) fn enumerate(
+        lua: &Lua,
+        (this, start): (LuaAnyUserData, Option<LuaInteger>),
+    ) -> LuaResult<LuaFunction> {
+        iterator(lua, this, Direction::Forward, Some(start.unwrap_or(1)))
+    }
+
+    /// The Lua-facing `__pairs` and `__ipairs` metamethods, and `__iter` on Luau.
+    ///
+    /// They are honoured by `pairs` and `ipairs` on Lua 5.2 and later, and by the
+    /// generalized iteration of Luau. They return the same iterator as `iter`.
+    #[cfg(not(any(feature = "lua51", feature = "luajit")))]
+    pub(super) fn pairs(
+        lua: &Lua,
+        this: LuaAnyUserData,
+    ) -> LuaResult<(LuaFunction, LuaAnyUserData, LuaValue)> {
+        let func = iterator(lua, this.clone(), Direction::Forward, None)?;
+
+        Ok((func, this, LuaNil))
+    }
+
+    /// The Lua-facing `__call` metamethod.
+    ///
+    /// It makes the vector its own stateless iterator, so `for i, x in vec do` works on
+    /// every Lua version. Given the previous index it returns the next index and value,
+    /// or `nil` at the end. Being stateless, it does not detect modifications.
+    pub(super) fn call(
+        _: &Lua,
+        this: &Self,
+        (_, control): (LuaValue, Option<LuaInteger>),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        let index = control.unwrap_or(0).saturating_add(1);
+        if index < 1 {
+            return Ok((LuaNil, LuaNil));
+        }
+
+        match this.vec.get(index) {
+            Some(value) => Ok((LuaValue::Integer(index), value)),
+            None => Ok((LuaNil, LuaNil)),
+        }
+    }
+
+    /// The Lua-facing `__add` metamethod.
+    ///
+    /// Adds two numeric vectors of equal length element by element, or adds a number to
+    /// every element when the other operand is a scalar.
+    pub(super) fn add(_: &Lua, (lhs, rhs): (LuaValue, LuaValue)) -> LuaResult<Self> {
+        arithmetic(ArithOp::Add, &lhs, &rhs)
+    }
+
+    /// The Lua-facing `__sub` metamethod, with the same operand rules as `__add`.
+    pub(super) fn sub(_: &Lua, (lhs, rhs): (LuaValue, LuaValue)) -> LuaResult<Self> {
+        arithmetic(ArithOp::Sub, &lhs, &rhs)
+    }
+
+    /// The Lua-facing `__mul` metamethod, with the same operand rules as `__add`.
+    pub(super) fn mul(_: &Lua, (lhs, rhs): (LuaValue, LuaValue)) -> LuaResult<Self> {
+        arithmetic(ArithOp::Mul, &lhs, &rhs)
+    }
+
+    /// The Lua-facing `__div` metamethod, with the same operand rules as `__add`.
+    pub(super) fn div(_: &Lua, (lhs, rhs): (LuaValue, LuaValue)) -> LuaResult<Self> {
+        arithmetic(ArithOp::Div, &lhs, &rhs)
+    }
+
+    /// The Lua-facing `__unm` metamethod, which negates every element.
+    pub(super) fn unm(_: &Lua, this: &Self, _: LuaMultiValue) -> LuaResult<Self> {
+        let vec = this.vec.negate().map_err(LuaError::RuntimeError)?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `dot` method, computing the dot product with a vector of equal length.
+    pub(super) fn dot(
+        _: &Lua,
+        this: &Self,
+        (other,): (LuaUserDataRef<Self>,),
+    ) -> LuaResult<LuaValue> {
+        this.vec.dot(&other.vec).map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `norm` method, computing the Euclidean length of the vector.
+    pub(super) fn norm(_: &Lua, this: &Self, _: ()) -> LuaResult<f64> {
+        this.vec.norm().map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `normalize` method.
+    ///
+    /// Returns a new `Vector` with the same direction and a norm of `1`. Causes a Lua
+    /// error for a vector with zero norm.
+    pub(super) fn normalize(_: &Lua, this: &Self, _: ()) -> LuaResult<Self> {
+        let vec = this.vec.normalize().map_err(LuaError::RuntimeError)?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `cross` method, computing the cross product of two vectors of length 3.
+    pub(super) fn cross(
+        _: &Lua,
+        this: &Self,
+        (other,): (LuaUserDataRef<Self>,),
+    ) -> LuaResult<Self> {
+        let vec = this.vec.cross(&other.vec).map_err(LuaError::RuntimeError)?;
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `sum` method. An empty vector sums to `0`.
+    pub(super) fn sum(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaValue> {
+        this.vec.sum().map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `min` method, returning the smallest element or `nil` if empty.
+    pub(super) fn min(_: &Lua, this: &Self, _: ()) -> LuaResult<Option<LuaValue>> {
+        this.vec.min().map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `max` method, returning the largest element or `nil` if empty.
+    pub(super) fn max(_: &Lua, this: &Self, _: ()) -> LuaResult<Option<LuaValue>> {
+        this.vec.max().map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `mean` method, returning the arithmetic mean or `nil` if empty.
+    pub(super) fn mean(_: &Lua, this: &Self, _: ()) -> LuaResult<Option<f64>> {
+        this.vec.mean().map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `to_table` method.
+    ///
+    /// It returns a new table with the elements at indices `1` to `#v`, followed by the
+    /// length of the vector, which tells where the sequence ends when it contains `nil`s.
+    pub(super) fn to_table(lua: &Lua, this: &Self, _: ()) -> LuaResult<(LuaTable, LuaInteger)> {
+        let values = this.vec.as_slice();
+        let table = lua.create_table_with_capacity(values.len(), 0)?;
+
+        for (idx, value) in values.iter().enumerate() {
+            table.raw_set(idx + 1, value)?;
+        }
+
+        Ok((table, this.vec.len()))
+    }
+
+    /// The Lua-facing `unpack` method.
+    ///
+    /// Returns the elements between `i` and `j`, inclusive, as multiple values. The
+    /// indices default to `1` and `-1` and are clamped like in `slice`.
+    pub(super) fn unpack(
+        _: &Lua,
+        this: &Self,
+        (i, j): (Option<LuaInteger>, Option<LuaInteger>),
+    ) -> LuaResult<LuaMultiValue> {
+        let (start, end) = this.vec.clamped_range(i.unwrap_or(1), j.unwrap_or(-1));
+
+        Ok(this.vec.as_slice()[start..end].iter().cloned().collect())
+    }
+
+    /// The Lua-facing `__tostring` metamethod.
+    ///
+    /// Renders the vector as `Vector(1, "two", nil)`. Nested vectors are rendered
+    /// recursively, with `Vector(<cycle>)` standing for a vector that contains itself,
+    /// and long vectors are truncated.
+    pub(super) fn to_string(_: &Lua, this: LuaAnyUserData) -> LuaResult<String> {
+        render_vector(&this, &mut Vec::new())
+    }
+
+    /// The Lua-facing `__concat` metamethod.
+    ///
+    /// Returns a new `Vector` with the elements of both operands, each of which may be a
+    /// `Vector` or a table sequence.
+    pub(super) fn concat(_: &Lua, (lhs, rhs): (LuaValue, LuaValue)) -> LuaResult<Self> {
+        let mut values = values_of(&lhs)?;
+        values.extend(values_of(&rhs)?);
+
+        Ok(Self {
+            vec: LuaVector::of(values),
+        })
+    }
+
+    /// The Lua-facing `vector.frozen_of` constructor.
+    ///
+    /// Behaves like `vector.of`, but the new `Vector` is frozen.
+    pub(super) fn frozen_of(_: &Lua, values: LuaVariadic<LuaValue>) -> LuaResult<Self> {
+        let mut vec = LuaVector::of(values.to_vec());
+        vec.freeze();
+
+        Ok(Self { vec })
+    }
+
+    /// The Lua-facing `freeze` method.
+    ///
+    /// Makes the vector immutable: every mutator called afterwards causes a Lua error.
+    /// Freezing cannot be undone, but `thaw_copy` returns a mutable copy.
+    pub(super) fn freeze(_: &Lua, this: &mut Self, _: ()) -> LuaResult<()> {
+        this.vec.freeze();
+        Ok(())
+    }
+
+    /// The Lua-facing `is_frozen` method.
+    pub(super) fn is_frozen(_: &Lua, this: &Self, _: ()) -> LuaResult<bool> {
+        Ok(this.vec.is_frozen())
+    }
+
+    /// The Lua-facing `thaw_copy` method.
+    ///
+    /// Returns a mutable copy of the vector, whether it is frozen or not.
+    pub(super) fn thaw_copy(_: &Lua, this: &Self, _: ()) -> LuaResult<Self> {
+        Ok(Self {
+            vec: this.vec.thawed_copy(),
+        })
+    }
+
+    /// Causes a Lua error if the vector is frozen, naming the `method` that was called.
+    pub(super) fn ensure_mutable(&self, method: &str) -> LuaResult<()> {
+        if self.vec.is_frozen() {
+            let msg = format!("Cannot call {} on a frozen Vector.", method);
+            return Err(LuaError::runtime(msg));
+        }
+        Ok(())
+    }
+
+    /// The Lua-facing `deep_equals` method.
+    ///
+    /// Unlike `__eq`, which compares nested tables and vectors by reference, it compares
+    /// them recursively by content. Cyclic structures are supported.
+    pub(super) fn deep_equals(
+        _: &Lua,
+        (this, other): (LuaAnyUserData, LuaValue),
+    ) -> LuaResult<bool> {
+        compare::deep_equals(&LuaValue::UserData(this), &other, &mut Vec::new())
+    }
+
+    /// The Lua-facing `__lt` metamethod.
+    ///
+    /// Orders vectors lexicographically: the first pair of different elements decides,
+    /// and a vector that is a prefix of the other one comes first. Elements are compared
+    /// like in `sort`, with nested vectors compared recursively. Causes a Lua error for
+    /// elements that cannot be ordered.
+    pub(super) fn lt(_: &Lua, (lhs, rhs): (LuaValue, LuaValue)) -> LuaResult<bool> {
+        Ok(compare::compare(&lhs, &rhs, &mut Vec::new())?.is_lt())
+    }
+
+    /// The Lua-facing `__le` metamethod, following the same ordering as `__lt`.
+    pub(super) fn le(_: &Lua, (lhs, rhs): (LuaValue, LuaValue)) -> LuaResult<bool> {
+        Ok(compare::compare(&lhs, &rhs, &mut Vec::new())?.is_le())
+    }
+
+    /// The Lua-facing `to_json` method.
+    ///
+    /// Encodes the vector as a JSON array, with `nil` elements written as `null`.
+    /// Nested vectors and sequence tables become arrays and other tables become objects.
+    /// Causes a Lua error for functions, userdata, cycles and non-finite numbers.
+    pub(super) fn to_json(_: &Lua, this: LuaAnyUserData) -> LuaResult<String> {
+        let packed = serial::encode(&LuaValue::UserData(this), &mut Vec::new())?;
+
+        Ok(serial::to_json(packed)?.to_string())
+    }
+
+    /// The Lua-facing `vector.from_json` constructor.
+    ///
+    /// Decodes a JSON array into a `Vector`, keeping `null` elements as present `nil`s.
+    /// Nested arrays become `Vector`s and objects become tables.
+    pub(super) fn from_json(lua: &Lua, (json,): (LuaString,)) -> LuaResult<Self> {
+        let value = serde_json::from_slice(&json.as_bytes())
+            .map_err(|err| LuaError::runtime(format!("Invalid JSON: {}", err)))?;
+
+        serial::decode_vector(lua, serial::from_json(value))
+    }
+
+    /// The Lua-facing `to_msgpack` method.
+    ///
+    /// Encodes the vector as a MessagePack array and returns the bytes as a Lua string.
+    /// It follows the same rules as `to_json`, except that strings that are not valid
+    /// UTF-8 and non-finite numbers are supported.
+    pub(super) fn to_msgpack(lua: &Lua, this: LuaAnyUserData) -> LuaResult<LuaString> {
+        let packed = serial::encode(&LuaValue::UserData(this), &mut Vec::new())?;
+
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &packed).map_err(LuaError::external)?;
+        lua.create_string(bytes)
+    }
+
+    /// The Lua-facing `vector.from_msgpack` constructor.
+    ///
+    /// Decodes a MessagePack array, as produced by `to_msgpack`, into a `Vector`.
+    /// Causes a Lua error if the bytes are malformed or followed by trailing data.
+    pub(super) fn from_msgpack(lua: &Lua, (bytes,): (LuaString,)) -> LuaResult<Self> {
+        let bytes = bytes.as_bytes();
+        let mut reader = &bytes[..];
+        let value = rmpv::decode::read_value(&mut reader)
+            .map_err(|err| LuaError::runtime(format!("Invalid MessagePack: {}", err)))?;
+        if !reader.is_empty() {
+            return Err(LuaError::runtime("Invalid MessagePack: trailing data."));
+        }
+
+        serial::decode_vector(lua, value)
+    }
+}
+
+/// Renders a `Vector` userdata, skipping the vectors in `visiting` to break cycles.
+fn render_vector(
+    this: &LuaAnyUserData,
+    visiting: &mut Vec<*const std::ffi::c_void>,
+) -> LuaResult<String> {
+    let ptr = this.to_pointer();
+    if visiting.contains(&ptr) {
+        return Ok("Vector(<cycle>)".to_string());
+    }
+
+    visiting.push(ptr);
+    let vec = this.borrow::<LuaVectorAdapter>()?;
+    let rendered = vec.vec.display_with(|value| match value {
+        LuaValue::String(string) => Ok(format!("{:?}", string.to_string_lossy())),
+        LuaValue::UserData(ud) if ud.is::<LuaVectorAdapter>() => render_vector(ud, visiting),
+        other => other.to_string(),
+    });
+    visiting.pop();
+
+    rendered
+}
+
+/// Wraps a list of vectors into a `Vector` whose elements are `Vector` userdata.
+fn nested_vector(lua: &Lua, vecs: Vec<LuaVector>) -> LuaResult<LuaVectorAdapter> {
+    let values = vecs
+        .into_iter()
+        .map(|vec| {
+            lua.create_userdata(LuaVectorAdapter { vec })
+                .map(LuaValue::UserData)
+        })
+        .collect::<LuaResult<_>>()?;
+
+    Ok(LuaVectorAdapter {
+        vec: LuaVector::of(values),
+    })
+}
+
+/// Applies an arithmetic metamethod where either operand may be a `Vector` or a scalar.
+fn arithmetic(op: ArithOp, lhs: &LuaValue, rhs: &LuaValue) -> LuaResult<LuaVectorAdapter> {
+    let vec = match (borrow_vector(lhs), borrow_vector(rhs)) {
+        (Some(a), Some(b)) => a.vec.zip_with(&b.vec, op),
+        (Some(a), None) => a.vec.broadcast(op, rhs, Side::Right),
+        (None, Some(b)) => b.vec.broadcast(op, lhs, Side::Left),
+        (None, None) => Err("Arithmetic metamethod called without a Vector.".to_string()),
+    };
+
+    vec.map(|vec| LuaVectorAdapter { vec })
+        .map_err(LuaError::RuntimeError)
+}
+
+/// Borrows a Lua value as a `Vector`, returning `None` for any other value.
+fn borrow_vector(value: &LuaValue) -> Option<LuaUserDataRef<LuaVectorAdapter>> {
+    match value {
+        LuaValue::UserData(ud) => ud.borrow::<LuaVectorAdapter>().ok(),
+        _ => None,
+    }
+}
+
+/// Creates a Lua iterator function over a `Vector` userdata.
+///
+/// When `counter` is given, the iterator yields it (incremented on every step) in place
+/// of the element index.
+fn iterator(
+    lua: &Lua,
+    this: LuaAnyUserData,
+    direction: Direction,
+    mut counter: Option<LuaInteger>,
+) -> LuaResult<LuaFunction> {
+    let mut cursor = {
+        let vec = this.borrow::<LuaVectorAdapter>()?;
+        LuaVectorCursor::new(&vec.vec, direction)
+    };
+
+    lua.create_function_mut(move |_, ()| {
+        let vec = this.borrow::<LuaVectorAdapter>()?;
+
+        match cursor.next(&vec.vec).map_err(LuaError::runtime)? {
+            Some((index, value)) => {
+                let key = match counter.as_mut() {
+                    Some(count) => {
+                        *count += 1;
+                        *count - 1
+                    }
+                    None => index,
+                };
+                Ok((LuaValue::Integer(key), value))
+            }
+            None => Ok((LuaNil, LuaNil)),
+        }
+    })
+}
+
+/// Collects the elements of a `Vector` userdata, or of the sequence part of a table.
+///
+/// The table sequence is read with raw accesses from `1` to its raw length.
+pub(super) fn values_of(value: &LuaValue) -> LuaResult<Vec<LuaValue>> {
+    match value {
+        LuaValue::UserData(ud) => {
+            let other = ud.borrow::<LuaVectorAdapter>()?;
+            Ok(other.vec.as_slice().to_vec())
+        }
+        LuaValue::Table(table) => (1..=table.raw_len())
+            .map(|idx| table.raw_get(idx))
+            .collect(),
+        other => {
+            let msg = format!("Expected a Vector or a table, got {}", other.type_name());
+            Err(LuaError::runtime(msg))
+        }
+    }
+}
+
+/// Compares two values with a Lua "less than" comparator, or with the default ordering.
+///
+/// The default ordering is the one of `__lt`, so nested `Vector`s compare lexicographically.
+fn less_than(cmp: &Option<LuaFunction>, a: &LuaValue, b: &LuaValue) -> LuaResult<bool> {
+    match cmp {
+        Some(func) => func
+            .call::<LuaValue>((a.clone(), b.clone()))
+            .map(|result| is_truthy(&result)),
+        None => Ok(compare::compare(a, b, &mut Vec::new())?.is_lt()),
+    }
+}
+
+/// Raises a Lua error if a sort reported an inconsistent comparator.
+fn ensure_consistent(method: &str, consistent: bool) -> LuaResult<()> {
+    if consistent {
+        Ok(())
+    } else {
+        let msg = format!("{}: invalid order function for sorting", method);
+        Err(LuaError::runtime(msg))
+    }
+}
+
+/// Calls a Lua predicate with `(value, index)` and converts its result using Lua truthiness.
+fn call_predicate(
+    method: &str,
+    pred: &LuaFunction,
+    index: LuaInteger,
+    value: &LuaValue,
+) -> LuaResult<bool> {
+    pred.call::<LuaValue>((value.clone(), index))
+        .map(|result| is_truthy(&result))
+        .map_err(|err| callback_error(method, index, err))
+}
+
+/// Wraps an error raised by a Lua callback, reporting which method and index triggered it.
+fn callback_error(method: &str, index: LuaInteger, err: LuaError) -> LuaError {
+    let msg = format!("{} callback failed at index {}: {}", method, index, err);
+    LuaError::runtime(msg)
+}
+
+/// Builds the error raised when a `group_by` key cannot be used as a table key.
+fn invalid_key(index: LuaInteger, key: &str) -> LuaError {
+    let msg = format!(
+        "group_by: key at index {} is {}, which cannot index a table.",
+        index, key
+    );
+    LuaError::runtime(msg)
+}
+
+/// Checks a value for Lua truthiness, where only `nil` and `false` are falsy.
+pub(super) fn is_truthy(value: &LuaValue) -> bool {
+    !matches!(value, LuaValue::Nil | LuaValue::Boolean(false))
+}
@@ -0,0 +1,231 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use mlua::prelude::*;
+
+use crate::typed::Numeric;
+use crate::vector::{LuaVector, zero_based_index_in};
+
+/// The byte order used to read and write multi-byte numbers.
+#[derive(Clone, Copy)]
+pub(crate) enum Endian {
+    /// The least significant byte comes first.
+    Little,
+    /// The most significant byte comes first.
+    Big,
+}
+
+impl Endian {
+    /// The suffix of the Lua method names for this byte order.
+    pub(crate) fn suffix(self) -> &'static str {
+        match self {
+            Self::Little => "le",
+            Self::Big => "be",
+        }
+    }
+}
+
+/// A numeric type that can be read from and written to a `LuaBytes` buffer.
+///
+/// The Lua conversions come from `Numeric`, so writing an integer that the type cannot
+/// represent exactly is rejected instead of being truncated, while floating-point values
+/// are rounded like `string.pack` does.
+pub(crate) trait Packable: Numeric {
+    /// The number of bytes taken by a value.
+    const SIZE: usize;
+
+    /// Decodes a value from exactly `SIZE` bytes.
+    fn from_bytes(bytes: &[u8], endian: Endian) -> Self;
+
+    /// Encodes a value into `SIZE` bytes.
+    fn to_bytes(self, endian: Endian) -> Vec<u8>;
+}
+
+/// Implements `Packable` through the standard `from_*_bytes` and `to_*_bytes` methods.
+macro_rules! impl_packable {
+    ($($ty:ty),*) => {
+        $(
+            impl Packable for $ty {
+                const SIZE: usize = std::mem::size_of::<$ty>();
+
+                fn from_bytes(bytes: &[u8], endian: Endian) -> Self {
+                    let mut array = [0; Self::SIZE];
+                    array.copy_from_slice(bytes);
+                    match endian {
+                        Endian::Little => Self::from_le_bytes(array),
+                        Endian::Big => Self::from_be_bytes(array),
+                    }
+                }
+
+                fn to_bytes(self, endian: Endian) -> Vec<u8> {
+                    match endian {
+                        Endian::Little => self.to_le_bytes().to_vec(),
+                        Endian::Big => self.to_be_bytes().to_vec(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_packable!(u8, i8, u16, i16, u32, i32, i64, f32, f64);
+
+/// Implements `Numeric` for the small integer types, which only exist in byte buffers.
+macro_rules! impl_small_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Numeric for $ty {
+                const NAME: &'static str = stringify!($ty);
+
+                fn from_lua_value(value: &LuaValue) -> Option<Self> {
+                    i64::from_lua_value(value).and_then(|int| Self::try_from(int).ok())
+                }
+
+                fn to_lua_value(self) -> LuaValue {
+                    LuaValue::Integer(self as LuaInteger)
+                }
+            }
+        )*
+    };
+}
+
+impl_small_int!(u8, i8, u16, i16, u32);
+
+/// The core implementation of a byte buffer.
+///
+/// Bytes are indexed like the elements of a `LuaVector`: 1-based, negative indices
+/// counting from the end, and presence through `Option`. Binary reads and writes take
+/// the offset of their first byte with the same convention and never resize the buffer.
+#[derive(Clone, PartialEq)]
+pub(crate) struct LuaBytes {
+    /// The internal `Vec` that stores the bytes.
+    inner: Vec<u8>,
+}
+
+impl LuaBytes {
+    /// Creates a new `LuaBytes` holding `len` zero bytes.
+    ///
+    /// Returns an `Err` if the length is negative or too large, or if the allocation fails.
+    pub(crate) fn new(len: LuaInteger) -> Result<Self, String> {
+        let len = LuaVector::checked_len(len)?;
+        let mut inner = Vec::new();
+        inner
+            .try_reserve_exact(len)
+            .map_err(|_| format!("Cannot allocate memory for {} bytes.", len))?;
+        inner.resize(len, 0);
+        Ok(Self::of(inner))
+    }
+
+    /// Creates a new `LuaBytes` from a pre-existing Rust `Vec<u8>`.
+    pub(crate) fn of(inner: Vec<u8>) -> Self {
+        Self { inner }
+    }
+
+    /// Decodes a hexadecimal string, accepting both lower and upper case digits.
+    pub(crate) fn from_hex(text: &[u8]) -> Result<Self, String> {
+        hex::decode(text)
+            .map(Self::of)
+            .map_err(|err| format!("Invalid hex string: {}.", err))
+    }
+
+    /// Decodes a standard, padded base64 string.
+    pub(crate) fn from_base64(text: &[u8]) -> Result<Self, String> {
+        BASE64
+            .decode(text)
+            .map(Self::of)
+            .map_err(|err| format!("Invalid base64 string: {}.", err))
+    }
+
+    /// Retrieves a byte based on a Lua 1-based index, following the `LuaVector` rules.
+    pub(crate) fn get(&self, index: LuaInteger) -> Option<u8> {
+        zero_based_index_in(self.inner.len(), index).map(|idx| self.inner[idx])
+    }
+
+    /// Sets the byte at a given Lua index.
+    ///
+    /// Returns `true` on success, `false` if the index is out of bounds.
+    pub(crate) fn set(&mut self, index: LuaInteger, byte: u8) -> bool {
+        match zero_based_index_in(self.inner.len(), index) {
+            Some(idx) => {
+                self.inner[idx] = byte;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Appends a byte to the end of the buffer.
+    ///
+    /// Returns `false` if the buffer has reached its maximum length.
+    pub(crate) fn push(&mut self, byte: u8) -> bool {
+        if self.inner.len() < LuaVector::MAX_LEN {
+            self.inner.push(byte);
+            return true;
+        }
+        false
+    }
+
+    /// Returns the number of bytes in the buffer.
+    pub(crate) fn len(&self) -> LuaInteger {
+        self.inner.len() as LuaInteger
+    }
+
+    /// Returns the bytes as a slice.
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        &self.inner
+    }
+
+    /// Encodes the bytes as a lower case hexadecimal string.
+    pub(crate) fn to_hex(&self) -> String {
+        hex::encode(&self.inner)
+    }
+
+    /// Encodes the bytes as a standard, padded base64 string.
+    pub(crate) fn to_base64(&self) -> String {
+        BASE64.encode(&self.inner)
+    }
+
+    /// Reads a number whose first byte is at the Lua index `offset`.
+    ///
+    /// Returns an `Err` if the value does not fit in the buffer from that offset.
+    pub(crate) fn read<T: Packable>(
+        &self,
+        offset: LuaInteger,
+        endian: Endian,
+    ) -> Result<T, String> {
+        let start = self.range_of::<T>("read", offset)?;
+
+        Ok(T::from_bytes(&self.inner[start..start + T::SIZE], endian))
+    }
+
+    /// Writes a number so that its first byte lands at the Lua index `offset`.
+    ///
+    /// Returns an `Err`, leaving the buffer unchanged, if the value does not fit in the
+    /// buffer from that offset.
+    pub(crate) fn write<T: Packable>(
+        &mut self,
+        offset: LuaInteger,
+        value: T,
+        endian: Endian,
+    ) -> Result<(), String> {
+        let start = self.range_of::<T>("write", offset)?;
+
+        self.inner[start..start + T::SIZE].copy_from_slice(&value.to_bytes(endian));
+        Ok(())
+    }
+
+    /// Converts the offset of a `T` value to a Rust 0-based index, checking that the
+    /// whole value is within the buffer.
+    fn range_of<T: Packable>(&self, verb: &str, offset: LuaInteger) -> Result<usize, String> {
+        zero_based_index_in(self.inner.len(), offset)
+            .filter(|&start| start + T::SIZE <= self.inner.len())
+            .ok_or_else(|| {
+                format!(
+                    "Cannot {} {} bytes at offset {} of a {}-byte buffer.",
+                    verb,
+                    T::SIZE,
+                    offset,
+                    self.inner.len()
+                )
+            })
+    }
+}
@@ -0,0 +1,195 @@
+use mlua::prelude::*;
+
+use crate::bytes::{Endian, LuaBytes, Packable};
+use crate::typed::{Numeric, conversion_error};
+
+/// A type alias for the `Bytes` struct, used for clarity in Lua bindings.
+pub(super) type LuaBytesAdapter = Bytes;
+
+/// The adapter struct that wraps a `LuaBytes` and implements the Lua-facing logic.
+///
+/// It exposes the element access surface of `Vector` with bytes as integers from `0`
+/// to `255`, along with the conversions to and from Lua strings and the binary readers
+/// and writers.
+pub(super) struct Bytes {
+    /// The internal `LuaBytes` that holds the actual data.
+    bytes: LuaBytes,
+}
+
+impl LuaBytesAdapter {
+    /// The Lua-facing constructor, registered as `vector.bytes(n | string)`.
+    ///
+    /// An integer creates a buffer of that many zero bytes, and a string is copied
+    /// byte by byte. Causes a Lua error if the length is negative, not an integer, or too
+    /// large to be allocated.
+    pub(super) fn new(_: &Lua, (source,): (LuaValue,)) -> LuaResult<Self> {
+        let bytes = match &source {
+            LuaValue::String(string) => Ok(LuaBytes::of(string.as_bytes().to_vec())),
+            LuaValue::Integer(len) => LuaBytes::new(*len),
+            LuaValue::Number(num) => match i64::from_lua_value(&source) {
+                Some(len) => LuaBytes::new(len),
+                None => Err(format!("Invalid Vector len {}.", num)),
+            },
+            other => Err(format!(
+                "Expected a length or a string, got {}",
+                other.type_name()
+            )),
+        };
+
+        bytes
+            .map(|bytes| Self { bytes })
+            .map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing constructor for hexadecimal strings, registered as `vector.bytes_from_hex`.
+    pub(super) fn from_hex(_: &Lua, (text,): (LuaString,)) -> LuaResult<Self> {
+        match LuaBytes::from_hex(&text.as_bytes()) {
+            Ok(bytes) => Ok(Self { bytes }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing constructor for base64 strings, registered as `vector.bytes_from_base64`.
+    pub(super) fn from_base64(_: &Lua, (text,): (LuaString,)) -> LuaResult<Self> {
+        match LuaBytes::from_base64(&text.as_bytes()) {
+            Ok(bytes) => Ok(Self { bytes }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing `get` method.
+    ///
+    /// It returns a presence flag and the byte, exactly like `Vector:get`.
+    pub(super) fn get(
+        _: &Lua,
+        this: &Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(match this.bytes.get(index) {
+            Some(byte) => (LuaValue::Boolean(true), byte.to_lua_value()),
+            None => (LuaValue::Boolean(false), LuaNil),
+        })
+    }
+
+    /// The Lua-facing `set` method.
+    ///
+    /// It returns `false` if the index is out of bounds, exactly like `Vector:set`, and
+    /// causes a Lua error if the value is not an integer from `0` to `255`.
+    pub(super) fn set(
+        _: &Lua,
+        this: &mut Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        let byte = to_byte(index, &value)?;
+
+        Ok(LuaValue::Boolean(this.bytes.set(index, byte)))
+    }
+
+    /// The Lua-facing `push` method.
+    ///
+    /// Appends a byte, causing a Lua error if the value is not an integer from `0` to `255`.
+    pub(super) fn push(_: &Lua, this: &mut Self, (value,): (LuaValue,)) -> LuaResult<LuaValue> {
+        let byte = to_byte(this.bytes.len() + 1, &value)?;
+
+        Ok(LuaValue::Boolean(this.bytes.push(byte)))
+    }
+
+    /// The Lua-facing `to_string` method, returning the bytes as a Lua string.
+    pub(super) fn to_string(lua: &Lua, this: &Self, _: ()) -> LuaResult<LuaString> {
+        lua.create_string(this.bytes.as_slice())
+    }
+
+    /// The Lua-facing `to_hex` method, returning the bytes as lower case hexadecimal.
+    pub(super) fn to_hex(_: &Lua, this: &Self, _: ()) -> LuaResult<String> {
+        Ok(this.bytes.to_hex())
+    }
+
+    /// The Lua-facing `to_base64` method, returning the bytes as padded base64.
+    pub(super) fn to_base64(_: &Lua, this: &Self, _: ()) -> LuaResult<String> {
+        Ok(this.bytes.to_base64())
+    }
+
+    /// The Lua-facing `read_*` methods, reading a `T` at the given offset.
+    ///
+    /// Causes a Lua error if the value does not fit in the buffer from that offset.
+    pub(super) fn read<T: Packable>(
+        &self,
+        offset: LuaInteger,
+        endian: Endian,
+    ) -> LuaResult<LuaValue> {
+        match self.bytes.read::<T>(offset, endian) {
+            Ok(value) => Ok(value.to_lua_value()),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing `write_*` methods, writing a `T` at the given offset.
+    ///
+    /// Causes a Lua error if the value is not a number, has no exact `T` representation
+    /// for an integer type, or does not fit in the buffer from that offset.
+    pub(super) fn write<T: Packable>(
+        &mut self,
+        offset: LuaInteger,
+        value: &LuaValue,
+        endian: Endian,
+    ) -> LuaResult<()> {
+        let Some(value) = T::round_lua_value(value) else {
+            return Err(LuaError::RuntimeError(conversion_error::<T>(offset, value)));
+        };
+
+        self.bytes
+            .write(offset, value, endian)
+            .map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `__index` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds.
+    pub(super) fn index(_: &Lua, this: &Self, (index,): (LuaInteger,)) -> LuaResult<LuaInteger> {
+        match this.bytes.get(index) {
+            Some(byte) => Ok(byte as LuaInteger),
+            None => {
+                let msg = format!("Index out of bounds {}", index);
+                Err(LuaError::runtime(msg))
+            }
+        }
+    }
+
+    /// The Lua-facing `__len` metamethod, returning the number of bytes.
+    pub(super) fn len(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.bytes.len())
+    }
+
+    /// The Lua-facing `__eq` metamethod, comparing the bytes of two buffers.
+    pub(super) fn equals(_: &Lua, this: &Self, (other,): (LuaValue,)) -> LuaResult<bool> {
+        Ok(match other {
+            LuaValue::UserData(ud) => ud
+                .borrow::<Self>()
+                .is_ok_and(|other| other.bytes == this.bytes),
+            _ => false,
+        })
+    }
+
+    /// The Lua-facing `__newindex` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds or the value is not a byte.
+    pub(super) fn newindex(
+        _: &Lua,
+        this: &mut Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<()> {
+        let byte = to_byte(index, &value)?;
+        if !this.bytes.set(index, byte) {
+            let msg = format!("Index out of bounds {}", index);
+            return Err(LuaError::runtime(msg));
+        }
+        Ok(())
+    }
+}
+
+/// Converts a Lua value to a byte, raising a Lua error if it is not an integer from
+/// `0` to `255`. `index` locates the value in the error message.
+fn to_byte(index: LuaInteger, value: &LuaValue) -> LuaResult<u8> {
+    u8::from_lua_value(value)
+        .ok_or_else(|| LuaError::RuntimeError(conversion_error::<u8>(index, value)))
+}
@@ -0,0 +1,144 @@
+use std::cmp::Ordering;
+use std::ffi::c_void;
+
+use mlua::prelude::*;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::vector::LuaVector;
+
+/// The pairs of containers currently being compared, used to detect cycles.
+type Visiting = Vec<(*const c_void, *const c_void)>;
+
+/// Compares two Lua values structurally.
+///
+/// Nested `Vector`s are equal if they have the same length and deeply equal elements,
+/// and tables if they have the same keys mapped to deeply equal values. Any other
+/// values are compared with the raw Lua equality. A pair of containers met again while
+/// it is being compared is assumed equal, so cyclic structures terminate.
+pub(crate) fn deep_equals(a: &LuaValue, b: &LuaValue, visiting: &mut Visiting) -> LuaResult<bool> {
+    match (a, b) {
+        (LuaValue::UserData(x), LuaValue::UserData(y))
+            if x.is::<LuaVectorAdapter>() && y.is::<LuaVectorAdapter>() =>
+        {
+            let pair = (x.to_pointer(), y.to_pointer());
+            if pair.0 == pair.1 || visiting.contains(&pair) {
+                return Ok(true);
+            }
+
+            let (x, y) = (
+                x.borrow::<LuaVectorAdapter>()?,
+                y.borrow::<LuaVectorAdapter>()?,
+            );
+            nested(pair, visiting, |visiting| {
+                vectors_equal(&x.vec, &y.vec, visiting)
+            })
+        }
+        (LuaValue::Table(x), LuaValue::Table(y)) => {
+            let pair = (x.to_pointer(), y.to_pointer());
+            if pair.0 == pair.1 || visiting.contains(&pair) {
+                return Ok(true);
+            }
+
+            nested(pair, visiting, |visiting| tables_equal(x, y, visiting))
+        }
+        _ => Ok(a == b),
+    }
+}
+
+/// Orders two Lua values, comparing nested `Vector`s lexicographically.
+///
+/// Equal values compare as `Equal`, numbers and strings follow `LuaVector::default_less`,
+/// and any other combination causes a Lua error. Vectors that contain each other
+/// cause a Lua error instead of recursing forever.
+pub(crate) fn compare(a: &LuaValue, b: &LuaValue, visiting: &mut Visiting) -> LuaResult<Ordering> {
+    if let (LuaValue::UserData(x), LuaValue::UserData(y)) = (a, b)
+        && x.is::<LuaVectorAdapter>()
+        && y.is::<LuaVectorAdapter>()
+    {
+        let pair = (x.to_pointer(), y.to_pointer());
+        if pair.0 == pair.1 {
+            return Ok(Ordering::Equal);
+        }
+        if visiting.contains(&pair) {
+            return Err(LuaError::runtime("Cannot compare cyclic Vectors."));
+        }
+
+        let (x, y) = (
+            x.borrow::<LuaVectorAdapter>()?,
+            y.borrow::<LuaVectorAdapter>()?,
+        );
+        return nested(pair, visiting, |visiting| {
+            compare_vectors(&x.vec, &y.vec, visiting)
+        });
+    }
+
+    if a == b {
+        return Ok(Ordering::Equal);
+    }
+    if LuaVector::default_less(a, b).map_err(LuaError::runtime)? {
+        Ok(Ordering::Less)
+    } else if LuaVector::default_less(b, a).map_err(LuaError::runtime)? {
+        Ok(Ordering::Greater)
+    } else {
+        Ok(Ordering::Equal)
+    }
+}
+
+/// Runs `compare_inner` with `pair` marked as being compared.
+fn nested<T>(
+    pair: (*const c_void, *const c_void),
+    visiting: &mut Visiting,
+    compare_inner: impl FnOnce(&mut Visiting) -> LuaResult<T>,
+) -> LuaResult<T> {
+    visiting.push(pair);
+    let result = compare_inner(visiting);
+    visiting.pop();
+
+    result
+}
+
+/// Checks that two vectors have the same length and deeply equal elements.
+fn vectors_equal(x: &LuaVector, y: &LuaVector, visiting: &mut Visiting) -> LuaResult<bool> {
+    if x.len() != y.len() {
+        return Ok(false);
+    }
+    for (a, b) in x.as_slice().iter().zip(y.as_slice()) {
+        if !deep_equals(a, b, visiting)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Checks that two tables have the same keys, mapped to deeply equal values.
+///
+/// Keys are matched with raw Lua equality, and metatables are ignored.
+fn tables_equal(x: &LuaTable, y: &LuaTable, visiting: &mut Visiting) -> LuaResult<bool> {
+    let mut len = 0;
+    for pair in x.pairs::<LuaValue, LuaValue>() {
+        let (key, value) = pair?;
+        let other = y.raw_get::<LuaValue>(key)?;
+        if other.is_nil() || !deep_equals(&value, &other, visiting)? {
+            return Ok(false);
+        }
+        len += 1;
+    }
+
+    let mut other_len = 0;
+    for pair in y.pairs::<LuaValue, LuaValue>() {
+        pair?;
+        other_len += 1;
+    }
+    Ok(len == other_len)
+}
+
+/// Compares two vectors lexicographically, a shorter prefix ordering first.
+fn compare_vectors(x: &LuaVector, y: &LuaVector, visiting: &mut Visiting) -> LuaResult<Ordering> {
+    for (a, b) in x.as_slice().iter().zip(y.as_slice()) {
+        let ordering = compare(a, b, visiting)?;
+        if ordering != Ordering::Equal {
+            return Ok(ordering);
+        }
+    }
+    Ok(x.len().cmp(&y.len()))
+}
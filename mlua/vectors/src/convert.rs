@@ -0,0 +1,75 @@
+use mlua::prelude::*;
+
+use crate::adapter::{LuaVectorAdapter, values_of};
+use crate::vector::LuaVector;
+
+/// A Rust `Vec` that converts to and from a Lua `Vector`.
+///
+/// Converting it into Lua creates a new `Vector` userdata holding every element, and
+/// converting a Lua value back copies the elements of a `Vector` (or of the sequence
+/// part of a table) into a `Vec<T>`, converting each of them with `T::from_lua`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LuaVec<T>(pub Vec<T>);
+
+impl<T> LuaVec<T> {
+    /// Returns the wrapped `Vec`.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> From<Vec<T>> for LuaVec<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self(values)
+    }
+}
+
+impl<T: IntoLua> IntoLua for LuaVec<T> {
+    fn into_lua(self, lua: &Lua) -> LuaResult<LuaValue> {
+        let values = self
+            .0
+            .into_iter()
+            .map(|value| value.into_lua(lua))
+            .collect::<LuaResult<_>>()?;
+
+        let vec = LuaVectorAdapter {
+            vec: LuaVector::of(values),
+        };
+        lua.create_userdata(vec).map(LuaValue::UserData)
+    }
+}
+
+impl<T: FromLua> FromLua for LuaVec<T> {
+    fn from_lua(value: LuaValue, lua: &Lua) -> LuaResult<Self> {
+        let values = values_of(&value).map_err(|err| LuaError::FromLuaConversionError {
+            from: value.type_name(),
+            to: "Vector".to_string(),
+            message: match err {
+                LuaError::RuntimeError(msg) => Some(msg),
+                other => Some(other.to_string()),
+            },
+        })?;
+
+        values
+            .into_iter()
+            .map(|value| T::from_lua(value, lua))
+            .collect::<LuaResult<_>>()
+            .map(Self)
+    }
+}
+
+/// Calls `f` with the elements of a `Vector` passed from Lua, without copying them.
+///
+/// The `Vector` is borrowed for the duration of the call, so `f` must not call back into
+/// Lua code that modifies it. Causes a Lua error if `value` is not a `Vector`.
+pub fn with_values<R>(value: &LuaValue, f: impl FnOnce(&[LuaValue]) -> R) -> LuaResult<R> {
+    let vec = match value {
+        LuaValue::UserData(ud) if ud.is::<LuaVectorAdapter>() => ud.borrow::<LuaVectorAdapter>()?,
+        _ => {
+            let msg = format!("Expected a Vector, got {}.", value.type_name());
+            return Err(LuaError::runtime(msg));
+        }
+    };
+
+    Ok(f(vec.vec.as_slice()))
+}
@@ -0,0 +1,109 @@
+use std::collections::VecDeque;
+
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, zero_based_index_in};
+
+/// The core implementation of a double-ended queue of Lua values.
+///
+/// It follows the indexing rules of `LuaVector` (1-based, negative indices counting
+/// from the back, presence through `Option`), and adds constant-time insertion and
+/// removal at the front, which a `Vec` can only provide in linear time.
+#[derive(Clone, PartialEq)]
+pub(crate) struct LuaDeque {
+    /// The internal `VecDeque` that stores the Lua values.
+    inner: VecDeque<LuaValue>,
+}
+
+impl LuaDeque {
+    /// The maximum length of the deque, constrained by the Lua `integer` type.
+    const MAX_LEN: usize = LuaInteger::MAX as usize;
+
+    /// Creates a new `LuaDeque` holding the given values, front to back.
+    pub(crate) fn of(values: Vec<LuaValue>) -> Self {
+        Self {
+            inner: values.into(),
+        }
+    }
+
+    /// Retrieves a value based on a Lua 1-based index, following the `LuaVector` rules.
+    pub(crate) fn get(&self, index: LuaInteger) -> Option<LuaValue> {
+        self.zero_based_index(index)
+            .map(|idx| self.inner[idx].clone())
+    }
+
+    /// Returns the number of elements in the deque.
+    pub(crate) fn len(&self) -> LuaInteger {
+        self.inner.len() as LuaInteger
+    }
+
+    /// Sets the value at a given Lua index.
+    ///
+    /// Returns `true` on success, `false` if the index is out of bounds.
+    pub(crate) fn set(&mut self, index: LuaInteger, new_value: LuaValue) -> bool {
+        match self.zero_based_index(index) {
+            Some(idx) => {
+                self.inner[idx] = new_value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Appends a new value to the back of the deque.
+    ///
+    /// Returns `false` if the deque has reached its maximum capacity.
+    pub(crate) fn push_back(&mut self, value: LuaValue) -> bool {
+        if self.inner.len() < Self::MAX_LEN {
+            self.inner.push_back(value);
+            return true;
+        }
+        false
+    }
+
+    /// Prepends a new value to the front of the deque.
+    ///
+    /// Returns `false` if the deque has reached its maximum capacity.
+    pub(crate) fn push_front(&mut self, value: LuaValue) -> bool {
+        if self.inner.len() < Self::MAX_LEN {
+            self.inner.push_front(value);
+            return true;
+        }
+        false
+    }
+
+    /// Removes and returns the last element, or `None` if the deque is empty.
+    pub(crate) fn pop_back(&mut self) -> Option<LuaValue> {
+        self.inner.pop_back()
+    }
+
+    /// Removes and returns the first element, or `None` if the deque is empty.
+    pub(crate) fn pop_front(&mut self) -> Option<LuaValue> {
+        self.inner.pop_front()
+    }
+
+    /// Returns the first element without removing it, or `None` if the deque is empty.
+    pub(crate) fn front(&self) -> Option<LuaValue> {
+        self.inner.front().cloned()
+    }
+
+    /// Returns the last element without removing it, or `None` if the deque is empty.
+    pub(crate) fn back(&self) -> Option<LuaValue> {
+        self.inner.back().cloned()
+    }
+
+    /// Compares two `LuaDeque` instances for pointer equality.
+    pub(crate) fn is_same(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+
+    /// Creates a `LuaVector` holding the same values, front to back.
+    pub(crate) fn to_vector(&self) -> LuaVector {
+        LuaVector::of(self.inner.iter().cloned().collect())
+    }
+
+    /// Converts a Lua 1-based index to a Rust 0-based index.
+    fn zero_based_index(&self, one_based_index: LuaInteger) -> Option<usize> {
+        zero_based_index_in(self.inner.len(), one_based_index)
+    }
+}
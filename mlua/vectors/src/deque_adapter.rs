@@ -0,0 +1,175 @@
+use mlua::prelude::*;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::deque::LuaDeque;
+
+/// A type alias for the `Deque` struct, used for clarity in Lua bindings.
+pub(super) type LuaDequeAdapter = Deque;
+
+/// The adapter struct that wraps a `LuaDeque` and implements the Lua-facing logic.
+///
+/// It exposes the element access surface of `Vector` (presence flags, `[]` operators
+/// raising errors out of bounds), plus the operations at the front of the queue.
+pub(super) struct Deque {
+    /// The internal `LuaDeque` that holds the actual data.
+    deque: LuaDeque,
+}
+
+impl LuaDequeAdapter {
+    /// The Lua-facing constructor, registered as `vector.deque(...)`.
+    ///
+    /// The arguments, if any, become the elements of the deque from front to back.
+    pub(super) fn new(_: &Lua, values: LuaVariadic<LuaValue>) -> LuaResult<Self> {
+        Ok(Self {
+            deque: LuaDeque::of(values.to_vec()),
+        })
+    }
+
+    /// The Lua-facing `get` method.
+    ///
+    /// It returns a presence flag and the value, exactly like `Vector:get`.
+    pub(super) fn get(
+        _: &Lua,
+        this: &Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(presence(this.deque.get(index)))
+    }
+
+    /// The Lua-facing `is_same` method, which checks for reference equality.
+    pub(super) fn is_same(_: &Lua, this: &Self, (other,): (LuaValue,)) -> LuaResult<LuaValue> {
+        let is_same = match other {
+            LuaValue::UserData(ud) => ud
+                .borrow::<Self>()
+                .is_ok_and(|other| this.deque.is_same(&other.deque)),
+            _ => false,
+        };
+
+        Ok(LuaValue::Boolean(is_same))
+    }
+
+    /// The Lua-facing `set` method.
+    ///
+    /// It returns `false` if the index is out of bounds, exactly like `Vector:set`.
+    pub(super) fn set(
+        _: &Lua,
+        this: &mut Self,
+        (index, new_value): (LuaInteger, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        Ok(LuaValue::Boolean(this.deque.set(index, new_value)))
+    }
+
+    /// The Lua-facing `push` method, which appends a value to the back.
+    ///
+    /// Returns `true` on success.
+    pub(super) fn push(_: &Lua, this: &mut Self, (value,): (LuaValue,)) -> LuaResult<LuaValue> {
+        Ok(LuaValue::Boolean(this.deque.push_back(value)))
+    }
+
+    /// The Lua-facing `push_front` method, which prepends a value to the front.
+    ///
+    /// Returns `true` on success.
+    pub(super) fn push_front(
+        _: &Lua,
+        this: &mut Self,
+        (value,): (LuaValue,),
+    ) -> LuaResult<LuaValue> {
+        Ok(LuaValue::Boolean(this.deque.push_front(value)))
+    }
+
+    /// The Lua-facing `pop` method.
+    ///
+    /// It removes and returns the last element, along with a presence flag.
+    pub(super) fn pop(_: &Lua, this: &mut Self, _: ()) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(presence(this.deque.pop_back()))
+    }
+
+    /// The Lua-facing `pop_front` method.
+    ///
+    /// It removes and returns the first element, along with a presence flag.
+    pub(super) fn pop_front(_: &Lua, this: &mut Self, _: ()) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(presence(this.deque.pop_front()))
+    }
+
+    /// The Lua-facing `peek_front` method.
+    ///
+    /// It returns the first element without removing it, along with a presence flag.
+    pub(super) fn peek_front(_: &Lua, this: &Self, _: ()) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(presence(this.deque.front()))
+    }
+
+    /// The Lua-facing `peek_back` method.
+    ///
+    /// It returns the last element without removing it, along with a presence flag.
+    pub(super) fn peek_back(_: &Lua, this: &Self, _: ()) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(presence(this.deque.back()))
+    }
+
+    /// The Lua-facing `to_vector` method.
+    ///
+    /// Returns a `Vector` holding the same values, front to back.
+    pub(super) fn to_vector(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaVectorAdapter> {
+        Ok(LuaVectorAdapter {
+            vec: this.deque.to_vector(),
+        })
+    }
+
+    /// The Lua-facing `__index` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds.
+    pub(super) fn index(_: &Lua, this: &Self, (index,): (LuaInteger,)) -> LuaResult<LuaValue> {
+        match this.deque.get(index) {
+            Some(value) => Ok(value),
+
+            None => {
+                let msg = format!("Index out of bounds {}", index);
+                let lua_err = LuaError::runtime(msg);
+                Err(lua_err)
+            }
+        }
+    }
+
+    /// The Lua-facing `__len` metamethod.
+    pub(super) fn len(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.deque.len())
+    }
+
+    /// The Lua-facing `__eq` metamethod.
+    ///
+    /// Two deques are equal if they hold the same values in the same order.
+    pub(super) fn equals(_: &Lua, this: &Self, (other,): (LuaValue,)) -> LuaResult<LuaValue> {
+        let equals = match other {
+            LuaValue::UserData(ud) => ud
+                .borrow::<Self>()
+                .is_ok_and(|other| this.deque == other.deque),
+            _ => false,
+        };
+
+        Ok(LuaValue::Boolean(equals))
+    }
+
+    /// The Lua-facing `__newindex` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds.
+    pub(super) fn newindex(
+        _: &Lua,
+        this: &mut Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<()> {
+        if !this.deque.set(index, value) {
+            let msg = format!("Index out of bounds {}", index);
+            let lua_err = LuaError::runtime(msg);
+            Err(lua_err)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Converts an optional value into the presence flag and value pair returned to Lua.
+fn presence(value: Option<LuaValue>) -> (LuaValue, LuaValue) {
+    match value {
+        Some(value) => (LuaValue::Boolean(true), value),
+        None => (LuaValue::Boolean(false), LuaNil),
+    }
+}
@@ -0,0 +1,136 @@
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, as_float};
+
+/// The number of ULPs tolerated when counting the elements of a float range, so that
+/// rounding errors in `(stop - start) / step` do not drop the last element.
+const RANGE_TOLERANCE_ULPS: f64 = 4.0;
+
+/// Constructors that compute the elements of a new vector instead of copying them.
+impl LuaVector {
+    /// Validates a requested vector length against `MAX_LEN`.
+    ///
+    /// Returns an `Err` if the length is negative or too large.
+    pub(crate) fn checked_len(len: LuaInteger) -> Result<usize, String> {
+        usize::try_from(len)
+            .ok()
+            .filter(|&len| len <= Self::MAX_LEN)
+            .ok_or_else(|| format!("Invalid Vector len {}.", len))
+    }
+
+    /// Creates the inclusive arithmetic progression from `start` to `stop`.
+    ///
+    /// When `start`, `stop` and `step` are all integers the elements are integers.
+    /// Otherwise they are floats computed as `start + i * step`, with the count derived
+    /// once from the bounds so that accumulated rounding errors neither add nor drop an
+    /// element. Every element lies on the progression: one that overshoots `stop` by more
+    /// than the rounding tolerance is dropped rather than clamped. A `step` going away
+    /// from `stop` produces an empty vector.
+    ///
+    /// Returns an `Err` if an argument is not a number, if `step` is zero or not finite,
+    /// or if the range holds more than `MAX_LEN` elements.
+    pub(crate) fn range(
+        start: &LuaValue,
+        stop: &LuaValue,
+        step: &LuaValue,
+    ) -> Result<Self, String> {
+        if let (LuaValue::Integer(start), LuaValue::Integer(stop), LuaValue::Integer(step)) =
+            (start, stop, step)
+        {
+            return Self::int_range(*start, *stop, *step);
+        }
+
+        let start = range_bound("start", start)?;
+        let stop = range_bound("stop", stop)?;
+        let step = range_bound("step", step)?;
+        if step == 0.0 || !step.is_finite() {
+            return Err(format!("Invalid range step {}.", step));
+        }
+
+        let steps = (stop - start) / step;
+        if !steps.is_finite() {
+            return Err(format!("Invalid range from {} to {}.", start, stop));
+        }
+        let tolerance = RANGE_TOLERANCE_ULPS * f64::EPSILON * steps.abs().max(1.0);
+        let count = if steps < 0.0 {
+            0.0
+        } else {
+            (steps + tolerance).floor() + 1.0
+        };
+        if count > Self::MAX_LEN as f64 {
+            return Err(too_long(count));
+        }
+
+        let mut vec = Self::with_capacity(count as LuaInteger)?;
+        for idx in 0..count as LuaInteger {
+            let num = start + idx as f64 * step;
+            if (num - stop) / step > tolerance {
+                break;
+            }
+            vec.push(LuaValue::Number(num));
+        }
+        Ok(vec)
+    }
+
+    /// Creates a new `LuaVector` of `len` elements, computed by `f` from their Lua index.
+    ///
+    /// Causes a Lua error if the length is negative or cannot be allocated. The first
+    /// error returned by the callback stops the generation and is propagated.
+    pub(crate) fn generate(
+        len: LuaInteger,
+        mut f: impl FnMut(LuaInteger) -> LuaResult<LuaValue>,
+    ) -> LuaResult<Self> {
+        Self::checked_len(len).map_err(LuaError::RuntimeError)?;
+        let mut vec = Self::with_capacity(len).map_err(LuaError::RuntimeError)?;
+
+        for index in 1..=len {
+            vec.push(f(index)?);
+        }
+        Ok(vec)
+    }
+
+    /// Creates the inclusive progression of integers from `start` to `stop`.
+    fn int_range(start: LuaInteger, stop: LuaInteger, step: LuaInteger) -> Result<Self, String> {
+        if step == 0 {
+            return Err(format!("Invalid range step {}.", step));
+        }
+
+        let distance = stop as i128 - start as i128;
+        let count = if distance != 0 && (distance < 0) != (step < 0) {
+            0
+        } else {
+            distance / step as i128 + 1
+        };
+        if count > Self::MAX_LEN as i128 {
+            return Err(too_long(count as f64));
+        }
+
+        let mut vec = Self::with_capacity(count as LuaInteger)?;
+        let mut value = start;
+        for _ in 0..count {
+            vec.push(LuaValue::Integer(value));
+            // Only the step past the last element can wrap, and its value is never pushed.
+            value = value.wrapping_add(step);
+        }
+        Ok(vec)
+    }
+}
+
+/// Reads a range argument as a number, naming it in the error otherwise.
+fn range_bound(name: &str, value: &LuaValue) -> Result<f64, String> {
+    as_float(value).ok_or_else(|| {
+        format!(
+            "Invalid range {}: expected a number, got {}.",
+            name,
+            value.type_name()
+        )
+    })
+}
+
+/// Builds the error message for a range with more than `MAX_LEN` elements.
+fn too_long(count: f64) -> String {
+    format!(
+        "A range of {} elements exceeds the maximum Vector len.",
+        count
+    )
+}
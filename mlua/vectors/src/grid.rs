@@ -0,0 +1,302 @@
+use std::collections::VecDeque;
+
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, as_float, zero_based_index_in};
+
+/// The row and column offsets of the four orthogonal neighbours, in row-major order.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// The row and column offsets of all eight neighbours, in row-major order.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// The core implementation of a two-dimensional grid of Lua values.
+///
+/// The cells are stored row by row in a single `LuaVector`. Rows and columns follow the
+/// `LuaVector` indexing rules independently: they are 1-based, negative values count
+/// from the last row or column, and out-of-bounds positions are reported through
+/// `Option` or `bool` results.
+#[derive(Clone)]
+pub(crate) struct LuaGrid {
+    /// The number of rows.
+    rows: usize,
+    /// The number of columns.
+    cols: usize,
+    /// The cells, in row-major order.
+    cells: LuaVector,
+}
+
+impl LuaGrid {
+    /// Creates a new `LuaGrid` of `rows` by `cols` cells, all set to `filler`.
+    ///
+    /// Returns an `Err` if a dimension is negative or if the grid would hold more than
+    /// the maximum number of elements of a `LuaVector`.
+    pub(crate) fn new(
+        rows: LuaInteger,
+        cols: LuaInteger,
+        filler: LuaValue,
+    ) -> Result<Self, String> {
+        let invalid = || format!("Invalid grid size {}x{}.", rows, cols);
+        if rows < 0 || cols < 0 {
+            return Err(invalid());
+        }
+
+        let len = rows.checked_mul(cols).ok_or_else(invalid)?;
+        Ok(Self {
+            rows: rows as usize,
+            cols: cols as usize,
+            cells: LuaVector::new(len, filler)?,
+        })
+    }
+
+    /// Creates the square identity matrix of size `n`, using integer cells.
+    pub(crate) fn identity(n: LuaInteger) -> Result<Self, String> {
+        let mut grid = Self::new(n, n, LuaValue::Integer(0))?;
+        for idx in 0..grid.rows {
+            grid.cells
+                .set(grid.flat_index(idx, idx), LuaValue::Integer(1));
+        }
+        Ok(grid)
+    }
+
+    /// Returns the number of rows.
+    pub(crate) fn rows(&self) -> LuaInteger {
+        self.rows as LuaInteger
+    }
+
+    /// Returns the number of columns.
+    pub(crate) fn cols(&self) -> LuaInteger {
+        self.cols as LuaInteger
+    }
+
+    /// Retrieves the value of a cell, or `None` if the position is out of bounds.
+    pub(crate) fn get(&self, row: LuaInteger, col: LuaInteger) -> Option<LuaValue> {
+        let (row, col) = self.position(row, col)?;
+        self.cells.get(self.flat_index(row, col))
+    }
+
+    /// Sets the value of a cell.
+    ///
+    /// Returns `true` on success, `false` if the position is out of bounds.
+    pub(crate) fn set(&mut self, row: LuaInteger, col: LuaInteger, value: LuaValue) -> bool {
+        match self.position(row, col) {
+            Some((row, col)) => self.cells.set(self.flat_index(row, col), value),
+            None => false,
+        }
+    }
+
+    /// Creates a new `LuaVector` holding the cells of a row, or `None` if out of bounds.
+    pub(crate) fn row(&self, row: LuaInteger) -> Option<LuaVector> {
+        let row = zero_based_index_in(self.rows, row)?;
+
+        Some(self.row_at(row))
+    }
+
+    /// Creates a new `LuaVector` holding the cells of a column, or `None` if out of bounds.
+    pub(crate) fn column(&self, col: LuaInteger) -> Option<LuaVector> {
+        let col = zero_based_index_in(self.cols, col)?;
+
+        Some(self.column_at(col))
+    }
+
+    /// Creates a new grid whose rows are the columns of this one.
+    ///
+    /// An empty grid is transposed without visiting its other dimension, which may be huge.
+    pub(crate) fn transpose(&self) -> Self {
+        if self.rows == 0 || self.cols == 0 {
+            return Self {
+                rows: self.cols,
+                cols: self.rows,
+                cells: LuaVector::of(Vec::new()),
+            };
+        }
+
+        let values = (0..self.cols)
+            .flat_map(|col| (0..self.rows).map(move |row| (row, col)))
+            .map(|(row, col)| self.cell(row, col))
+            .collect();
+
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            cells: LuaVector::of(values),
+        }
+    }
+
+    /// Replaces the region of cells connected to a starting cell with `value`.
+    ///
+    /// The region holds the starting cell and every cell reachable from it through
+    /// orthogonal neighbours equal (with the raw Lua equality) to its original value.
+    /// Returns the number of cells filled, or `None` if the position is out of bounds.
+    pub(crate) fn flood_fill(
+        &mut self,
+        row: LuaInteger,
+        col: LuaInteger,
+        value: LuaValue,
+    ) -> Option<usize> {
+        let start = self.position(row, col)?;
+        let target = self.cell(start.0, start.1);
+        let mut visited = vec![false; self.rows * self.cols];
+        let mut queue = VecDeque::from([start]);
+        let mut filled = Vec::new();
+
+        visited[start.0 * self.cols + start.1] = true;
+        while let Some((row, col)) = queue.pop_front() {
+            filled.push((row, col));
+
+            for (next_row, next_col) in self.adjacent(row, col, &ORTHOGONAL) {
+                let flat = next_row * self.cols + next_col;
+                if !visited[flat] && self.cell(next_row, next_col) == target {
+                    visited[flat] = true;
+                    queue.push_back((next_row, next_col));
+                }
+            }
+        }
+
+        for &(row, col) in &filled {
+            self.cells.set(self.flat_index(row, col), value.clone());
+        }
+        Some(filled.len())
+    }
+
+    /// Returns the positions and values of the neighbours of a cell, in row-major order.
+    ///
+    /// Only the four orthogonal neighbours are included unless `diagonal` is `true`, and
+    /// positions outside the grid are skipped. The positions are returned as positive Lua
+    /// indices. Returns `None` if the cell itself is out of bounds.
+    pub(crate) fn neighbours(
+        &self,
+        row: LuaInteger,
+        col: LuaInteger,
+        diagonal: bool,
+    ) -> Option<Vec<(LuaInteger, LuaInteger, LuaValue)>> {
+        let (row, col) = self.position(row, col)?;
+        let offsets: &[(isize, isize)] = if diagonal { &SURROUNDING } else { &ORTHOGONAL };
+
+        Some(
+            self.adjacent(row, col, offsets)
+                .map(|(row, col)| {
+                    let value = self.cell(row, col);
+                    (row as LuaInteger + 1, col as LuaInteger + 1, value)
+                })
+                .collect(),
+        )
+    }
+
+    /// Computes the matrix product of this grid by `other`.
+    ///
+    /// Returns an `Err` if the number of columns of this grid differs from the number of
+    /// rows of `other`, if the product cannot be allocated, or describing the first cell
+    /// that is not a number.
+    pub(crate) fn matmul(&self, other: &Self) -> Result<Self, String> {
+        if self.cols != other.rows {
+            return Err(format!(
+                "Cannot multiply a {}x{} grid by a {}x{} grid.",
+                self.rows, self.cols, other.rows, other.cols
+            ));
+        }
+        self.ensure_numbers()?;
+        other.ensure_numbers()?;
+
+        let len = self
+            .rows
+            .checked_mul(other.cols)
+            .filter(|&len| len <= LuaVector::MAX_LEN)
+            .ok_or_else(|| format!("Invalid grid size {}x{}.", self.rows, other.cols))?;
+        let mut values = Vec::new();
+        values
+            .try_reserve(len)
+            .map_err(|_| format!("Cannot allocate memory for {} more elements.", len))?;
+
+        // An empty product has no cell to compute, however many rows or columns it has.
+        if len > 0 {
+            let columns = (0..other.cols)
+                .map(|col| other.column_at(col))
+                .collect::<Vec<_>>();
+            for row in (0..self.rows).map(|row| self.row_at(row)) {
+                for column in &columns {
+                    values.push(row.dot(column)?);
+                }
+            }
+        }
+
+        Ok(Self {
+            rows: self.rows,
+            cols: other.cols,
+            cells: LuaVector::of(values),
+        })
+    }
+
+    /// Converts a Lua position to Rust 0-based row and column indices.
+    fn position(&self, row: LuaInteger, col: LuaInteger) -> Option<(usize, usize)> {
+        Some((
+            zero_based_index_in(self.rows, row)?,
+            zero_based_index_in(self.cols, col)?,
+        ))
+    }
+
+    /// Converts Rust 0-based row and column indices to the Lua index of the cell.
+    fn flat_index(&self, row: usize, col: usize) -> LuaInteger {
+        (row * self.cols + col) as LuaInteger + 1
+    }
+
+    /// Returns the value of a cell at Rust 0-based row and column indices.
+    fn cell(&self, row: usize, col: usize) -> LuaValue {
+        self.cells.as_slice()[row * self.cols + col].clone()
+    }
+
+    /// Creates a new `LuaVector` holding the cells of a row at a Rust 0-based index.
+    fn row_at(&self, row: usize) -> LuaVector {
+        let start = row * self.cols;
+
+        LuaVector::of(self.cells.as_slice()[start..start + self.cols].to_vec())
+    }
+
+    /// Creates a new `LuaVector` holding the cells of a column at a Rust 0-based index.
+    fn column_at(&self, col: usize) -> LuaVector {
+        LuaVector::of((0..self.rows).map(|row| self.cell(row, col)).collect())
+    }
+
+    /// Iterates over the in-bounds cells at the given offsets from a cell.
+    fn adjacent<'a>(
+        &self,
+        row: usize,
+        col: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (rows, cols) = (self.rows, self.cols);
+
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let row = row.checked_add_signed(dr).filter(|&row| row < rows)?;
+            let col = col.checked_add_signed(dc).filter(|&col| col < cols)?;
+            Some((row, col))
+        })
+    }
+
+    /// Returns an `Err` describing the first cell that is not a number, if any.
+    fn ensure_numbers(&self) -> Result<(), String> {
+        match self
+            .cells
+            .as_slice()
+            .iter()
+            .position(|value| as_float(value).is_none())
+        {
+            Some(idx) => Err(format!(
+                "Cannot multiply: cell ({}, {}) is a {}, expected a number.",
+                idx / self.cols + 1,
+                idx % self.cols + 1,
+                self.cells.as_slice()[idx].type_name()
+            )),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use mlua::prelude::*;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::grid::LuaGrid;
+
+/// A type alias for the `Grid` struct, used for clarity in Lua bindings.
+pub(super) type LuaGridAdapter = Grid;
+
+/// The adapter struct that wraps a `LuaGrid` and implements the Lua-facing logic.
+///
+/// Cells are addressed by a row and a column, each following the `Vector` indexing
+/// conventions, and accessors report out-of-bounds positions the same way.
+pub(super) struct Grid {
+    /// The internal `LuaGrid` that holds the actual data.
+    grid: LuaGrid,
+}
+
+impl LuaGridAdapter {
+    /// The Lua-facing constructor, registered as `vector.grid(rows, cols, filler)`.
+    ///
+    /// Causes a Lua error if a dimension is negative or the grid is too large.
+    pub(super) fn new(
+        _: &Lua,
+        (rows, cols, filler): (LuaInteger, LuaInteger, Option<LuaValue>),
+    ) -> LuaResult<Self> {
+        match LuaGrid::new(rows, cols, filler.unwrap_or(LuaNil)) {
+            Ok(grid) => Ok(Self { grid }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing constructor for the identity matrix, registered as `vector.identity(n)`.
+    pub(super) fn identity(_: &Lua, (n,): (LuaInteger,)) -> LuaResult<Self> {
+        match LuaGrid::identity(n) {
+            Ok(grid) => Ok(Self { grid }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing `get` method.
+    ///
+    /// It returns a presence flag and the value of the cell, exactly like `Vector:get`.
+    pub(super) fn get(
+        _: &Lua,
+        this: &Self,
+        (row, col): (LuaInteger, LuaInteger),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(match this.grid.get(row, col) {
+            Some(value) => (LuaValue::Boolean(true), value),
+            None => (LuaValue::Boolean(false), LuaNil),
+        })
+    }
+
+    /// The Lua-facing `set` method.
+    ///
+    /// It returns `false` if the position is out of bounds, exactly like `Vector:set`.
+    pub(super) fn set(
+        _: &Lua,
+        this: &mut Self,
+        (row, col, value): (LuaInteger, LuaInteger, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        Ok(LuaValue::Boolean(this.grid.set(row, col, value)))
+    }
+
+    /// The Lua-facing `rows` method, returning the number of rows.
+    pub(super) fn rows(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.grid.rows())
+    }
+
+    /// The Lua-facing `cols` method, returning the number of columns.
+    pub(super) fn cols(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.grid.cols())
+    }
+
+    /// The Lua-facing `row` method.
+    ///
+    /// Returns a new `Vector` holding the cells of a row. Causes a Lua error if the row
+    /// is out of bounds.
+    pub(super) fn row(_: &Lua, this: &Self, (row,): (LuaInteger,)) -> LuaResult<LuaVectorAdapter> {
+        match this.grid.row(row) {
+            Some(vec) => Ok(LuaVectorAdapter { vec }),
+            None => Err(LuaError::runtime(format!("Row out of bounds {}", row))),
+        }
+    }
+
+    /// The Lua-facing `column` method.
+    ///
+    /// Returns a new `Vector` holding the cells of a column. Causes a Lua error if the
+    /// column is out of bounds.
+    pub(super) fn column(
+        _: &Lua,
+        this: &Self,
+        (col,): (LuaInteger,),
+    ) -> LuaResult<LuaVectorAdapter> {
+        match this.grid.column(col) {
+            Some(vec) => Ok(LuaVectorAdapter { vec }),
+            None => Err(LuaError::runtime(format!("Column out of bounds {}", col))),
+        }
+    }
+
+    /// The Lua-facing `transpose` method, returning a new grid.
+    pub(super) fn transpose(_: &Lua, this: &Self, _: ()) -> LuaResult<Self> {
+        Ok(Self {
+            grid: this.grid.transpose(),
+        })
+    }
+
+    /// The Lua-facing `flood_fill` method.
+    ///
+    /// Replaces the region connected to the cell at `(row, col)` with `value` and returns
+    /// the number of cells filled. Causes a Lua error if the position is out of bounds.
+    pub(super) fn flood_fill(
+        _: &Lua,
+        this: &mut Self,
+        (row, col, value): (LuaInteger, LuaInteger, LuaValue),
+    ) -> LuaResult<LuaInteger> {
+        match this.grid.flood_fill(row, col, value) {
+            Some(filled) => Ok(filled as LuaInteger),
+            None => Err(out_of_bounds(row, col)),
+        }
+    }
+
+    /// The Lua-facing `neighbours` method.
+    ///
+    /// Returns an iterator function yielding the row, column and value of every neighbour
+    /// of the cell at `(row, col)`, including diagonal ones when `diagonal` is truthy. The
+    /// neighbours are read when the method is called. Causes a Lua error if the position
+    /// is out of bounds.
+    pub(super) fn neighbours(
+        lua: &Lua,
+        this: &Self,
+        (row, col, diagonal): (LuaInteger, LuaInteger, Option<bool>),
+    ) -> LuaResult<LuaFunction> {
+        let Some(neighbours) = this.grid.neighbours(row, col, diagonal.unwrap_or(false)) else {
+            return Err(out_of_bounds(row, col));
+        };
+        let mut neighbours = neighbours.into_iter();
+
+        lua.create_function_mut(move |_, ()| {
+            Ok(match neighbours.next() {
+                Some((row, col, value)) => (LuaValue::Integer(row), LuaValue::Integer(col), value),
+                None => (LuaNil, LuaNil, LuaNil),
+            })
+        })
+    }
+
+    /// The Lua-facing `matmul` method.
+    ///
+    /// Returns the matrix product of this grid by `other`. Causes a Lua error if the
+    /// dimensions do not match or if a cell is not a number.
+    pub(super) fn matmul(
+        _: &Lua,
+        this: &Self,
+        (other,): (LuaUserDataRef<Self>,),
+    ) -> LuaResult<Self> {
+        match this.grid.matmul(&other.grid) {
+            Ok(grid) => Ok(Self { grid }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+}
+
+/// Builds the error raised for a cell position outside the grid.
+fn out_of_bounds(row: LuaInteger, col: LuaInteger) -> LuaError {
+    LuaError::runtime(format!("Index out of bounds ({}, {})", row, col))
+}
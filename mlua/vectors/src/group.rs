@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use mlua::prelude::*;
+
+use crate::search::EqBucket;
+use crate::vector::LuaVector;
+
+/// Splitting a vector into smaller vectors, by position or by content.
+///
+/// Every operation creates new vectors holding copies of the elements, leaving the
+/// original one unchanged.
+impl LuaVector {
+    /// Splits the vector into consecutive chunks of `size` elements.
+    ///
+    /// The last chunk is shorter when the length is not a multiple of `size`. Returns
+    /// an `Err` if `size` is not positive.
+    pub(crate) fn chunks(&self, size: LuaInteger) -> Result<Vec<Self>, String> {
+        if size < 1 {
+            return Err(format!("Invalid chunk size {}.", size));
+        }
+
+        Ok(self
+            .as_slice()
+            .chunks(size as usize)
+            .map(|chunk| Self::of(chunk.to_vec()))
+            .collect())
+    }
+
+    /// Returns every contiguous window of `size` elements, overlapping each other.
+    ///
+    /// A vector shorter than `size` has no windows. Returns an `Err` if `size` is not
+    /// positive.
+    pub(crate) fn windows(&self, size: LuaInteger) -> Result<Vec<Self>, String> {
+        if size < 1 {
+            return Err(format!("Invalid window size {}.", size));
+        }
+
+        Ok(self
+            .as_slice()
+            .windows(size as usize)
+            .map(|window| Self::of(window.to_vec()))
+            .collect())
+    }
+
+    /// Splits the vector into the elements accepted by `pred` and the rejected ones.
+    ///
+    /// The callback receives the Lua 1-based index and the element, and both vectors keep
+    /// the original relative order.
+    pub(crate) fn partition<E>(
+        &self,
+        mut pred: impl FnMut(LuaInteger, &LuaValue) -> Result<bool, E>,
+    ) -> Result<(Self, Self), E> {
+        let (mut accepted, mut rejected) = (Vec::new(), Vec::new());
+
+        for (idx, value) in self.as_slice().iter().enumerate() {
+            if pred(idx as LuaInteger + 1, value)? {
+                accepted.push(value.clone());
+            } else {
+                rejected.push(value.clone());
+            }
+        }
+        Ok((Self::of(accepted), Self::of(rejected)))
+    }
+
+    /// Groups the elements by the key computed by `key_of`.
+    ///
+    /// Keys are compared with the raw Lua equality, like in `unique`. The groups are
+    /// returned in the order their keys first appear, each keeping the original relative
+    /// order of its elements.
+    pub(crate) fn group_by<E>(
+        &self,
+        mut key_of: impl FnMut(LuaInteger, &LuaValue) -> Result<LuaValue, E>,
+    ) -> Result<Vec<(LuaValue, Self)>, E> {
+        let mut groups: Vec<(LuaValue, Vec<LuaValue>)> = Vec::new();
+        let mut buckets: HashMap<EqBucket, Vec<usize>> = HashMap::new();
+
+        for (idx, value) in self.as_slice().iter().enumerate() {
+            let key = key_of(idx as LuaInteger + 1, value)?;
+            let bucket = buckets.entry(EqBucket::of(&key)).or_default();
+
+            match bucket.iter().find(|&&group| groups[group].0 == key) {
+                Some(&group) => groups[group].1.push(value.clone()),
+                None => {
+                    bucket.push(groups.len());
+                    groups.push((key, vec![value.clone()]));
+                }
+            }
+        }
+        Ok(groups
+            .into_iter()
+            .map(|(key, values)| (key, Self::of(values)))
+            .collect())
+    }
+
+    /// Pairs every element with the element at the same position in `other`.
+    ///
+    /// Each pair is a two-element vector, and the result is as long as the shorter
+    /// vector.
+    pub(crate) fn zip(&self, other: &Self) -> Vec<Self> {
+        self.as_slice()
+            .iter()
+            .zip(other.as_slice())
+            .map(|(a, b)| Self::of(vec![a.clone(), b.clone()]))
+            .collect()
+    }
+
+    /// Splits a list of pairs into the vector of first elements and the vector of second
+    /// elements. This is the inverse of `zip`.
+    ///
+    /// Returns an `Err` describing the first entry that does not hold exactly two values.
+    pub(crate) fn unzip(pairs: Vec<Vec<LuaValue>>) -> Result<(Self, Self), String> {
+        let (mut firsts, mut seconds) = (Vec::with_capacity(pairs.len()), Vec::new());
+
+        for (idx, pair) in pairs.into_iter().enumerate() {
+            let [first, second] = <[LuaValue; 2]>::try_from(pair).map_err(|pair| {
+                format!(
+                    "Element at index {} holds {} values, expected a pair.",
+                    idx + 1,
+                    pair.len()
+                )
+            })?;
+            firsts.push(first);
+            seconds.push(second);
+        }
+        Ok((Self::of(firsts), Self::of(seconds)))
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+
+use mlua::prelude::*;
+
+use crate::vector::LuaVector;
+
+/// A reversible edit recorded in the history of a `LuaVector`.
+pub(crate) enum Edit {
+    /// A single element was replaced, added or removed at a Lua 1-based index.
+    ///
+    /// `old` is `None` for an added element, and `new` is `None` for a removed one.
+    Element {
+        index: LuaInteger,
+        old: Option<LuaValue>,
+        new: Option<LuaValue>,
+    },
+    /// Several elements changed at once, as in `sort` or `splice`.
+    ///
+    /// Holds the elements on the other side of the edit: the previous ones until it is
+    /// undone, and the following ones until it is redone.
+    Contents(Vec<LuaValue>),
+    /// The last elements were removed, as in `truncate` or `clear`.
+    ///
+    /// Holds only the removed elements rather than the whole contents.
+    Tail(Vec<LuaValue>),
+}
+
+/// The undo and redo history of a vector.
+///
+/// Edits are gathered into a pending step until the mutation that made them ends, or
+/// until the outermost group ends, so that undoing a step reverts all of them at once.
+/// Only the latest `limit` steps are kept.
+pub(crate) struct LuaHistory {
+    /// The steps that can be undone, the most recent last.
+    undo: VecDeque<Vec<Edit>>,
+    /// The steps that can be redone, the most recently undone last.
+    redo: Vec<Vec<Edit>>,
+    /// The edits of the step being recorded.
+    pending: Vec<Edit>,
+    /// The maximum number of steps that can be undone.
+    limit: usize,
+    /// The number of nested groups currently open.
+    group_depth: usize,
+}
+
+impl LuaHistory {
+    /// The number of steps kept when no limit is given.
+    pub(crate) const DEFAULT_LIMIT: LuaInteger = 100;
+
+    /// Creates an empty history keeping at most `limit` steps.
+    ///
+    /// Returns an `Err` if the limit is not a positive number.
+    pub(crate) fn new(limit: LuaInteger) -> Result<Self, String> {
+        let mut history = Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            pending: Vec::new(),
+            limit: 0,
+            group_depth: 0,
+        };
+        history.set_limit(limit)?;
+        Ok(history)
+    }
+
+    /// Changes the maximum number of steps, forgetting the oldest ones if needed.
+    ///
+    /// Returns an `Err` if the limit is not a positive number.
+    pub(crate) fn set_limit(&mut self, limit: LuaInteger) -> Result<(), String> {
+        if limit < 1 {
+            return Err(format!("Invalid history limit {}.", limit));
+        }
+        self.limit = limit as usize;
+        self.trim();
+        Ok(())
+    }
+
+    /// Adds an edit to the step being recorded, unless the step is already snapshotted.
+    pub(crate) fn record(&mut self, edit: Edit) {
+        if !self.is_snapshotted() {
+            self.pending.push(edit);
+        }
+    }
+
+    /// Returns `true` if the step being recorded ends with a copy of the contents.
+    ///
+    /// Undoing that copy restores the contents before it whatever happened next, and
+    /// redoing it restores the contents at the end of the step, so the following edits
+    /// of the step need not be recorded. This keeps a single copy per batch.
+    pub(crate) fn is_snapshotted(&self) -> bool {
+        matches!(self.pending.last(), Some(Edit::Contents(_)))
+    }
+
+    /// Closes the step being recorded, unless a group is open.
+    ///
+    /// A step holding edits discards the steps that could be redone.
+    pub(crate) fn commit(&mut self) {
+        if self.group_depth > 0 || self.pending.is_empty() {
+            return;
+        }
+        self.undo.push_back(std::mem::take(&mut self.pending));
+        self.redo.clear();
+        self.trim();
+    }
+
+    /// Opens a group, possibly nested in another one.
+    pub(crate) fn begin_group(&mut self) {
+        self.group_depth += 1;
+    }
+
+    /// Closes a group, committing its edits as a single step if it was the outermost one.
+    pub(crate) fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+        self.commit();
+    }
+
+    /// Returns an `Err` if a group is open, as its edits cannot be reordered with undone steps.
+    fn ensure_closed(&self, verb: &str) -> Result<(), String> {
+        if self.group_depth > 0 {
+            return Err(format!("Cannot {} inside a batch.", verb));
+        }
+        Ok(())
+    }
+
+    /// Forgets the oldest steps beyond the limit.
+    fn trim(&mut self) {
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+}
+
+/// Undo and redo operations over vectors whose history is tracked.
+impl LuaVector {
+    /// Starts tracking the history with at most `limit` steps, or changes the limit if it
+    /// is already tracked.
+    pub(crate) fn track_history(&mut self, limit: LuaInteger) -> Result<(), String> {
+        match self.history_mut() {
+            Some(history) => history.set_limit(limit),
+            None => {
+                *self.history_mut() = Some(LuaHistory::new(limit)?);
+                Ok(())
+            }
+        }
+    }
+
+    /// Reverts the latest step.
+    ///
+    /// Returns `Ok(false)` if there is nothing to undo, and an `Err` inside a batch.
+    pub(crate) fn undo(&mut self) -> Result<bool, String> {
+        self.with_history("undo", |vec, history| {
+            let Some(step) = history.undo.pop_back() else {
+                return false;
+            };
+            let mut step: Vec<_> = step
+                .into_iter()
+                .rev()
+                .map(|edit| vec.replay(edit, true))
+                .collect();
+            step.reverse();
+            history.redo.push(step);
+            true
+        })
+    }
+
+    /// Reapplies the latest undone step.
+    ///
+    /// Returns `Ok(false)` if there is nothing to redo, and an `Err` inside a batch.
+    pub(crate) fn redo(&mut self) -> Result<bool, String> {
+        self.with_history("redo", |vec, history| {
+            let Some(step) = history.redo.pop() else {
+                return false;
+            };
+            let step = step
+                .into_iter()
+                .map(|edit| vec.replay(edit, false))
+                .collect();
+            history.undo.push_back(step);
+            history.trim();
+            true
+        })
+    }
+
+    /// Runs `f` with the history taken out of the vector, so that the edits it replays
+    /// are not recorded again.
+    fn with_history(
+        &mut self,
+        verb: &str,
+        f: impl FnOnce(&mut Self, &mut LuaHistory) -> bool,
+    ) -> Result<bool, String> {
+        let Some(mut history) = self.history_mut().take() else {
+            return Ok(false);
+        };
+        let result = history.ensure_closed(verb).map(|()| f(self, &mut history));
+        *self.history_mut() = Some(history);
+        result
+    }
+
+    /// Applies an edit backwards to undo it, or forwards to redo it.
+    ///
+    /// Returns the edit to keep for the opposite operation.
+    fn replay(&mut self, edit: Edit, undo: bool) -> Edit {
+        match edit {
+            Edit::Element { index, old, new } => {
+                let (from, to) = if undo { (&new, &old) } else { (&old, &new) };
+                match (from, to) {
+                    (None, Some(value)) => {
+                        self.insert(index, value.clone());
+                    }
+                    (Some(_), None) => {
+                        self.remove(index);
+                    }
+                    (Some(_), Some(value)) => {
+                        self.set(index, value.clone());
+                    }
+                    (None, None) => {}
+                }
+                Edit::Element { index, old, new }
+            }
+            Edit::Contents(values) => Edit::Contents(self.replace(values)),
+            Edit::Tail(values) => {
+                if undo {
+                    self.extend(values.clone());
+                } else {
+                    // The removed elements were restored, so the length cannot be negative.
+                    let len = self.len() - values.len() as LuaInteger;
+                    let _ = self.truncate(len);
+                }
+                Edit::Tail(values)
+            }
+        }
+    }
+}
+
+/// An immutable copy of the elements of a vector, taken by `snapshot`.
+///
+/// The copy is shallow: tables and userdata held by the vector are shared with it.
+pub(crate) struct LuaSnapshot {
+    /// The elements of the vector when the snapshot was taken.
+    values: Vec<LuaValue>,
+}
+
+impl LuaSnapshot {
+    /// Takes a snapshot of the elements of a vector.
+    pub(crate) fn of(vec: &LuaVector) -> Self {
+        Self {
+            values: vec.as_slice().to_vec(),
+        }
+    }
+
+    /// Returns the number of elements in the snapshot.
+    pub(crate) fn len(&self) -> LuaInteger {
+        self.values.len() as LuaInteger
+    }
+
+    /// Returns the elements of the snapshot as a Rust slice.
+    pub(crate) fn as_slice(&self) -> &[LuaValue] {
+        &self.values
+    }
+}
@@ -0,0 +1,101 @@
+use mlua::prelude::*;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::history::{LuaHistory, LuaSnapshot};
+use crate::vector::LuaVector;
+
+/// A type alias for the `Snapshot` struct, used for clarity in Lua bindings.
+pub(super) type LuaSnapshotAdapter = Snapshot;
+
+/// The adapter struct that wraps a `LuaSnapshot`, returned by `Vector:snapshot`.
+pub(super) struct Snapshot {
+    /// The internal `LuaSnapshot` that holds the copied elements.
+    snapshot: LuaSnapshot,
+}
+
+impl LuaVectorAdapter {
+    /// The Lua-facing `track_history` method.
+    ///
+    /// Records every following mutation, so that it can be reverted with `undo`. At most
+    /// `limit` steps are kept, 100 by default. Calling it again changes the limit while
+    /// keeping the recorded steps.
+    pub(super) fn track_history(
+        _: &Lua,
+        this: &mut Self,
+        limit: Option<LuaInteger>,
+    ) -> LuaResult<()> {
+        let limit = limit.unwrap_or(LuaHistory::DEFAULT_LIMIT);
+        this.vec
+            .track_history(limit)
+            .map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `undo` method.
+    ///
+    /// Reverts the latest mutation, or the latest `batch`, and returns `true`. Returns
+    /// `false` if there is nothing to undo or the history is not tracked.
+    pub(super) fn undo(_: &Lua, this: &mut Self, _: ()) -> LuaResult<bool> {
+        this.vec.undo().map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `redo` method.
+    ///
+    /// Reapplies the latest undone step and returns `true`. Returns `false` if there is
+    /// nothing to redo: any mutation made after `undo` discards the undone steps.
+    pub(super) fn redo(_: &Lua, this: &mut Self, _: ()) -> LuaResult<bool> {
+        this.vec.redo().map_err(LuaError::RuntimeError)
+    }
+
+    /// The Lua-facing `snapshot` method, returning an immutable copy of the elements.
+    pub(super) fn snapshot(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaSnapshotAdapter> {
+        Ok(Snapshot {
+            snapshot: LuaSnapshot::of(&this.vec),
+        })
+    }
+
+    /// The Lua-facing `restore` method.
+    ///
+    /// Replaces the elements with the ones of a snapshot. Restoring is itself a single
+    /// step of the history, so it can be undone.
+    pub(super) fn restore(
+        _: &Lua,
+        this: &mut Self,
+        snapshot: LuaUserDataRef<LuaSnapshotAdapter>,
+    ) -> LuaResult<()> {
+        let values = snapshot.snapshot.as_slice();
+        if this.vec.as_slice() != values {
+            this.vec.replace(values.to_vec());
+        }
+        Ok(())
+    }
+}
+
+impl LuaSnapshotAdapter {
+    /// The Lua-facing `__len` metamethod.
+    pub(super) fn len(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.snapshot.len())
+    }
+
+    /// The Lua-facing `to_vector` method, returning a new `Vector` holding the elements.
+    pub(super) fn to_vector(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaVectorAdapter> {
+        Ok(LuaVectorAdapter {
+            vec: LuaVector::of(this.snapshot.as_slice().to_vec()),
+        })
+    }
+}
+
+/// Opens a history group on a `Vector` userdata for `batch`, if its history is tracked.
+pub(super) fn begin_group(vec: &LuaAnyUserData) -> LuaResult<()> {
+    if let Some(history) = vec.borrow_mut::<LuaVectorAdapter>()?.vec.history_mut() {
+        history.begin_group();
+    }
+    Ok(())
+}
+
+/// Closes a history group opened by `begin_group`, committing its edits as one step.
+pub(super) fn end_group(vec: &LuaAnyUserData) -> LuaResult<()> {
+    if let Some(history) = vec.borrow_mut::<LuaVectorAdapter>()?.vec.history_mut() {
+        history.end_group();
+    }
+    Ok(())
+}
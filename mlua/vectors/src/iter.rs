@@ -0,0 +1,70 @@
+use mlua::prelude::*;
+
+use crate::vector::LuaVector;
+
+/// The direction in which a `LuaVectorCursor` walks over a vector.
+#[derive(Clone, Copy)]
+pub(crate) enum Direction {
+    /// From the first element to the last one.
+    Forward,
+    /// From the last element to the first one.
+    Backward,
+}
+
+/// The core state of an iterator over a `LuaVector`.
+///
+/// The cursor does not borrow the vector between steps. Instead, it records the
+/// vector's modification counter when created and checks it on every step, so a
+/// vector that grew or shrank during the traversal is reported as an error rather
+/// than silently skipping or repeating elements.
+pub(crate) struct LuaVectorCursor {
+    /// The Lua 1-based index of the next element to yield.
+    next: LuaInteger,
+    /// The direction of the traversal.
+    direction: Direction,
+    /// The modification counter of the vector when the cursor was created.
+    modifications: usize,
+}
+
+impl LuaVectorCursor {
+    /// Creates a new cursor positioned at the first element to visit in `direction`.
+    pub(crate) fn new(vec: &LuaVector, direction: Direction) -> Self {
+        let next = match direction {
+            Direction::Forward => 1,
+            Direction::Backward => vec.len(),
+        };
+
+        Self {
+            next,
+            direction,
+            modifications: vec.modifications(),
+        }
+    }
+
+    /// Advances the cursor, returning the Lua index and value of the current element.
+    ///
+    /// Returns `Ok(None)` once the traversal is over, and an `Err` if the vector was
+    /// structurally modified since the cursor was created.
+    pub(crate) fn next(
+        &mut self,
+        vec: &LuaVector,
+    ) -> Result<Option<(LuaInteger, LuaValue)>, String> {
+        if vec.modifications() != self.modifications {
+            return Err("Vector was modified during iteration.".to_string());
+        }
+        if self.next < 1 {
+            return Ok(None);
+        }
+
+        let index = self.next;
+        let Some(value) = vec.get(index) else {
+            return Ok(None);
+        };
+
+        self.next = match self.direction {
+            Direction::Forward => index + 1,
+            Direction::Backward => index - 1,
+        };
+        Ok(Some((index, value)))
+    }
+}
@@ -0,0 +1,211 @@
+use mlua::prelude::*;
+
+use crate::adapter::{LuaVectorAdapter, is_truthy};
+use crate::iter::{Direction, LuaVectorCursor};
+
+/// The values produced by one step of a lazy pipeline.
+///
+/// Most stages produce a single value, while `enumerate` and `zip` produce several,
+/// which are passed to the callbacks of the following stages as separate arguments.
+pub(crate) type Item = Vec<LuaValue>;
+
+/// A lazy pipeline, pulling its items one by one from a source through its stages.
+///
+/// Every combinator wraps the previous pipeline in a new stage, and nothing is evaluated
+/// until a terminal operation pulls items with `next`. A pipeline reading a `Vector`
+/// uses a `LuaVectorCursor`, so structural changes to the vector are reported as errors.
+pub(crate) enum LuaPipeline {
+    /// Reads the elements of a `Vector` userdata.
+    Vector {
+        vec: LuaAnyUserData,
+        cursor: LuaVectorCursor,
+    },
+    /// Reads a list of values copied up front, such as the sequence part of a table.
+    Values(std::vec::IntoIter<LuaValue>),
+    /// Replaces every item with the result of `func`.
+    Map {
+        inner: Box<LuaPipeline>,
+        func: LuaFunction,
+    },
+    /// Keeps the items for which `pred` is truthy.
+    Filter {
+        inner: Box<LuaPipeline>,
+        pred: LuaFunction,
+    },
+    /// Yields at most `remaining` more items.
+    Take {
+        inner: Box<LuaPipeline>,
+        remaining: usize,
+    },
+    /// Discards the first `remaining` items.
+    Skip {
+        inner: Box<LuaPipeline>,
+        remaining: usize,
+    },
+    /// Yields items until `pred` is falsy for the first time.
+    TakeWhile {
+        inner: Box<LuaPipeline>,
+        pred: LuaFunction,
+        done: bool,
+    },
+    /// Discards items until `pred` is falsy for the first time.
+    SkipWhile {
+        inner: Box<LuaPipeline>,
+        pred: LuaFunction,
+        skipping: bool,
+    },
+    /// Joins the items of two pipelines, stopping when either one is exhausted.
+    Zip {
+        left: Box<LuaPipeline>,
+        right: Box<LuaPipeline>,
+    },
+    /// Yields the items of `first`, then those of `second`.
+    Chain {
+        first: Box<LuaPipeline>,
+        second: Box<LuaPipeline>,
+        first_done: bool,
+    },
+    /// Prepends a counter, starting at `1`, to every item.
+    Enumerate {
+        inner: Box<LuaPipeline>,
+        count: LuaInteger,
+    },
+    /// Yields the first item and then every `step`-th one.
+    StepBy {
+        inner: Box<LuaPipeline>,
+        step: usize,
+        started: bool,
+    },
+}
+
+impl LuaPipeline {
+    /// Creates a pipeline reading the elements of a `Vector` userdata.
+    pub(crate) fn vector(vec: LuaAnyUserData) -> LuaResult<Self> {
+        let cursor =
+            LuaVectorCursor::new(&vec.borrow::<LuaVectorAdapter>()?.vec, Direction::Forward);
+
+        Ok(Self::Vector { vec, cursor })
+    }
+
+    /// Pulls the next item through every stage, or returns `None` once exhausted.
+    pub(crate) fn next(&mut self) -> LuaResult<Option<Item>> {
+        match self {
+            Self::Vector { vec, cursor } => {
+                let vec = vec.borrow::<LuaVectorAdapter>()?;
+                let step = cursor.next(&vec.vec).map_err(LuaError::runtime)?;
+                Ok(step.map(|(_, value)| vec![value]))
+            }
+            Self::Values(values) => Ok(values.next().map(|value| vec![value])),
+            Self::Map { inner, func } => match inner.next()? {
+                Some(item) => Ok(Some(vec![call("map", func, item)?])),
+                None => Ok(None),
+            },
+            Self::Filter { inner, pred } => {
+                while let Some(item) = inner.next()? {
+                    if is_truthy(&call("filter", pred, item.clone())?) {
+                        return Ok(Some(item));
+                    }
+                }
+                Ok(None)
+            }
+            Self::Take { inner, remaining } => {
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                *remaining -= 1;
+                inner.next()
+            }
+            Self::Skip { inner, remaining } => {
+                while *remaining > 0 {
+                    *remaining -= 1;
+                    if inner.next()?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                inner.next()
+            }
+            Self::TakeWhile { inner, pred, done } => {
+                if *done {
+                    return Ok(None);
+                }
+                match inner.next()? {
+                    Some(item) if is_truthy(&call("take_while", pred, item.clone())?) => {
+                        Ok(Some(item))
+                    }
+                    _ => {
+                        *done = true;
+                        Ok(None)
+                    }
+                }
+            }
+            Self::SkipWhile {
+                inner,
+                pred,
+                skipping,
+            } => {
+                while *skipping {
+                    match inner.next()? {
+                        Some(item) if is_truthy(&call("skip_while", pred, item.clone())?) => {}
+                        item => {
+                            *skipping = false;
+                            return Ok(item);
+                        }
+                    }
+                }
+                inner.next()
+            }
+            Self::Zip { left, right } => match (left.next()?, right.next()?) {
+                (Some(mut item), Some(other)) => {
+                    item.extend(other);
+                    Ok(Some(item))
+                }
+                _ => Ok(None),
+            },
+            Self::Chain {
+                first,
+                second,
+                first_done,
+            } => {
+                if !*first_done {
+                    match first.next()? {
+                        Some(item) => return Ok(Some(item)),
+                        None => *first_done = true,
+                    }
+                }
+                second.next()
+            }
+            Self::Enumerate { inner, count } => match inner.next()? {
+                Some(item) => {
+                    let mut numbered = vec![LuaValue::Integer(*count)];
+                    numbered.extend(item);
+                    *count += 1;
+                    Ok(Some(numbered))
+                }
+                None => Ok(None),
+            },
+            Self::StepBy {
+                inner,
+                step,
+                started,
+            } => {
+                if *started {
+                    for _ in 1..*step {
+                        if inner.next()?.is_none() {
+                            return Ok(None);
+                        }
+                    }
+                }
+                *started = true;
+                inner.next()
+            }
+        }
+    }
+}
+
+/// Calls a stage callback with the values of an item, keeping its first result.
+fn call(method: &str, func: &LuaFunction, item: Item) -> LuaResult<LuaValue> {
+    func.call(LuaMultiValue::from_vec(item)).map_err(|err| {
+        let msg = format!("{} callback failed: {}", method, err);
+        LuaError::runtime(msg)
+    })
+}
@@ -0,0 +1,266 @@
+use mlua::prelude::*;
+
+use crate::adapter::{LuaVectorAdapter, values_of};
+use crate::lazy::{Item, LuaPipeline};
+use crate::vector::LuaVector;
+
+/// A type alias for the `Lazy` struct, used for clarity in Lua bindings.
+pub(super) type LuaLazyAdapter = Lazy;
+
+/// The adapter struct that exposes a `LuaPipeline` to Lua.
+///
+/// Combinators and terminal operations consume the pipeline: the receiver is left
+/// empty, and using it again causes a Lua error. This mirrors Rust iterators, and
+/// guarantees that every pipeline is evaluated at most once.
+pub(super) struct Lazy {
+    /// The pipeline, or `None` once it has been consumed.
+    pipeline: Option<LuaPipeline>,
+}
+
+impl LuaLazyAdapter {
+    /// The Lua-facing constructor for `Vector:lazy()`.
+    ///
+    /// It is registered as a function rather than a method because the pipeline needs
+    /// to hold the vector userdata itself.
+    pub(super) fn new(_: &Lua, vec: LuaAnyUserData) -> LuaResult<Self> {
+        Ok(Self::of(LuaPipeline::vector(vec)?))
+    }
+
+    /// The Lua-facing `map` combinator, replacing every item with `func(...)`.
+    pub(super) fn map(_: &Lua, this: &mut Self, (func,): (LuaFunction,)) -> LuaResult<Self> {
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::Map { inner, func }))
+    }
+
+    /// The Lua-facing `filter` combinator, keeping the items for which `pred(...)` is truthy.
+    pub(super) fn filter(_: &Lua, this: &mut Self, (pred,): (LuaFunction,)) -> LuaResult<Self> {
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::Filter { inner, pred }))
+    }
+
+    /// The Lua-facing `take` combinator, keeping at most `n` items.
+    pub(super) fn take_n(_: &Lua, this: &mut Self, (n,): (LuaInteger,)) -> LuaResult<Self> {
+        let remaining = count_arg("take", n)?;
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::Take { inner, remaining }))
+    }
+
+    /// The Lua-facing `skip` combinator, discarding the first `n` items.
+    pub(super) fn skip(_: &Lua, this: &mut Self, (n,): (LuaInteger,)) -> LuaResult<Self> {
+        let remaining = count_arg("skip", n)?;
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::Skip { inner, remaining }))
+    }
+
+    /// The Lua-facing `take_while` combinator, stopping at the first item for which
+    /// `pred(...)` is falsy.
+    pub(super) fn take_while(_: &Lua, this: &mut Self, (pred,): (LuaFunction,)) -> LuaResult<Self> {
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::TakeWhile {
+            inner,
+            pred,
+            done: false,
+        }))
+    }
+
+    /// The Lua-facing `skip_while` combinator, discarding items until `pred(...)` is
+    /// falsy for the first time.
+    pub(super) fn skip_while(_: &Lua, this: &mut Self, (pred,): (LuaFunction,)) -> LuaResult<Self> {
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::SkipWhile {
+            inner,
+            pred,
+            skipping: true,
+        }))
+    }
+
+    /// The Lua-facing `zip` combinator.
+    ///
+    /// Joins every item with the next one of `other`, which may be another lazy
+    /// iterator (consumed), a `Vector` or a table sequence. Stops when either side
+    /// is exhausted.
+    pub(super) fn zip(_: &Lua, this: &mut Self, (other,): (LuaValue,)) -> LuaResult<Self> {
+        let right = pipeline_of(other)?;
+        let left = this.take()?;
+
+        Ok(Self::of(LuaPipeline::Zip { left, right }))
+    }
+
+    /// The Lua-facing `chain` combinator.
+    ///
+    /// Yields the items of the receiver, then those of `other`, which accepts the same
+    /// values as `zip`.
+    pub(super) fn chain(_: &Lua, this: &mut Self, (other,): (LuaValue,)) -> LuaResult<Self> {
+        let second = pipeline_of(other)?;
+        let first = this.take()?;
+
+        Ok(Self::of(LuaPipeline::Chain {
+            first,
+            second,
+            first_done: false,
+        }))
+    }
+
+    /// The Lua-facing `enumerate` combinator, prepending a counter starting at `1`.
+    pub(super) fn enumerate(_: &Lua, this: &mut Self, _: ()) -> LuaResult<Self> {
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::Enumerate { inner, count: 1 }))
+    }
+
+    /// The Lua-facing `step_by` combinator, keeping the first item and then every
+    /// `step`-th one. Causes a Lua error if `step` is not positive.
+    pub(super) fn step_by(_: &Lua, this: &mut Self, (step,): (LuaInteger,)) -> LuaResult<Self> {
+        if step < 1 {
+            let msg = format!("step_by: invalid step {}.", step);
+            return Err(LuaError::runtime(msg));
+        }
+        let inner = this.take()?;
+
+        Ok(Self::of(LuaPipeline::StepBy {
+            inner,
+            step: step as usize,
+            started: false,
+        }))
+    }
+
+    /// The Lua-facing `collect` terminal operation.
+    ///
+    /// Returns a new `Vector` with every item. Items made of several values, such as
+    /// those of `enumerate` and `zip`, are collected as nested `Vector`s.
+    pub(super) fn collect(lua: &Lua, this: &mut Self, _: ()) -> LuaResult<LuaVectorAdapter> {
+        let mut pipeline = this.take()?;
+        let mut values = Vec::new();
+
+        while let Some(item) = pipeline.next()? {
+            values.push(collapse(lua, item)?);
+        }
+        Ok(LuaVectorAdapter {
+            vec: LuaVector::of(values),
+        })
+    }
+
+    /// The Lua-facing `fold` terminal operation.
+    ///
+    /// Calls `func(acc, ...)` for every item, starting with `acc = init`, and returns
+    /// the final accumulator.
+    pub(super) fn fold(
+        _: &Lua,
+        this: &mut Self,
+        (func, init): (LuaFunction, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        let mut pipeline = this.take()?;
+        let mut acc = init;
+
+        while let Some(item) = pipeline.next()? {
+            let args = std::iter::once(acc).chain(item).collect::<LuaMultiValue>();
+            acc = func.call(args).map_err(|err| {
+                let msg = format!("fold callback failed: {}", err);
+                LuaError::runtime(msg)
+            })?;
+        }
+        Ok(acc)
+    }
+
+    /// The Lua-facing `count` terminal operation, returning the number of items.
+    pub(super) fn count(_: &Lua, this: &mut Self, _: ()) -> LuaResult<LuaInteger> {
+        let mut pipeline = this.take()?;
+        let mut count = 0;
+
+        while pipeline.next()?.is_some() {
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// The Lua-facing `first` terminal operation.
+    ///
+    /// Returns `true` followed by the values of the first item, or `false` if there is
+    /// none. Only the first item is evaluated.
+    pub(super) fn first(_: &Lua, this: &mut Self, _: ()) -> LuaResult<LuaMultiValue> {
+        let item = this.take()?.next()?;
+
+        Ok(presence(item))
+    }
+
+    /// The Lua-facing `last` terminal operation.
+    ///
+    /// Returns `true` followed by the values of the last item, or `false` if there is
+    /// none.
+    pub(super) fn last(_: &Lua, this: &mut Self, _: ()) -> LuaResult<LuaMultiValue> {
+        let mut pipeline = this.take()?;
+        let mut last = None;
+
+        while let Some(item) = pipeline.next()? {
+            last = Some(item);
+        }
+        Ok(presence(last))
+    }
+
+    /// Wraps a pipeline into a new, unconsumed adapter.
+    fn of(pipeline: LuaPipeline) -> Self {
+        Self {
+            pipeline: Some(pipeline),
+        }
+    }
+
+    /// Moves the pipeline out of the adapter, causing a Lua error if already consumed.
+    fn take(&mut self) -> LuaResult<Box<LuaPipeline>> {
+        self.pipeline
+            .take()
+            .map(Box::new)
+            .ok_or_else(|| LuaError::runtime("Lazy iterator was already consumed."))
+    }
+}
+
+/// Creates the pipeline for the `other` argument of `zip` and `chain`.
+fn pipeline_of(other: LuaValue) -> LuaResult<Box<LuaPipeline>> {
+    if let LuaValue::UserData(ud) = &other {
+        if ud.is::<LuaLazyAdapter>() {
+            return ud.borrow_mut::<LuaLazyAdapter>()?.take();
+        }
+        if ud.is::<LuaVectorAdapter>() {
+            return LuaPipeline::vector(ud.clone()).map(Box::new);
+        }
+    }
+
+    let values = values_of(&other)?;
+    Ok(Box::new(LuaPipeline::Values(values.into_iter())))
+}
+
+/// Validates the count argument of `take` and `skip`.
+fn count_arg(method: &str, n: LuaInteger) -> LuaResult<usize> {
+    if n < 0 {
+        let msg = format!("{}: invalid count {}.", method, n);
+        return Err(LuaError::runtime(msg));
+    }
+    Ok(n as usize)
+}
+
+/// Converts an item into a single value, packing several values into a `Vector`.
+fn collapse(lua: &Lua, mut item: Item) -> LuaResult<LuaValue> {
+    if item.len() == 1 {
+        return Ok(item.remove(0));
+    }
+
+    let vec = LuaVectorAdapter {
+        vec: LuaVector::of(item),
+    };
+    lua.create_userdata(vec).map(LuaValue::UserData)
+}
+
+/// Converts an optional item into a presence flag followed by its values.
+fn presence(item: Option<Item>) -> LuaMultiValue {
+    match item {
+        Some(item) => std::iter::once(LuaValue::Boolean(true))
+            .chain(item)
+            .collect(),
+        None => LuaMultiValue::from_vec(vec![LuaValue::Boolean(false)]),
+    }
+}
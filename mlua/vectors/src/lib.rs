@@ -1,24 +1,206 @@
 mod adapter;
+mod bytes;
+mod bytes_adapter;
+mod compare;
+mod convert;
+mod deque;
+mod deque_adapter;
+mod generate;
+mod grid;
+mod grid_adapter;
+mod group;
+mod history;
+mod history_adapter;
+mod iter;
+mod lazy;
+mod lazy_adapter;
+mod linalg;
+mod observe;
+mod observe_adapter;
+mod persistent;
+mod persistent_adapter;
+mod ring;
+mod ring_adapter;
+mod search;
+mod serial;
+mod typed;
+mod typed_adapter;
 mod vector;
+mod view;
+mod view_adapter;
 
 use adapter::LuaVectorAdapter;
+use bytes::{Endian, Packable};
+use bytes_adapter::LuaBytesAdapter;
+use deque_adapter::LuaDequeAdapter;
+use grid_adapter::LuaGridAdapter;
+use history_adapter::LuaSnapshotAdapter;
+use lazy_adapter::LuaLazyAdapter;
 use mlua::prelude::*;
+use observe::LuaObservers;
+use observe_adapter::LuaSubscriptionAdapter;
+use persistent_adapter::LuaPersistentVectorAdapter;
+use ring_adapter::LuaRingAdapter;
+use typed::Numeric;
+use typed_adapter::LuaTypedVectorAdapter;
+use view_adapter::LuaVectorViewAdapter;
+
+pub use convert::{LuaVec, with_values};
 
 /// The entry point for the Lua module, exposing the `Vector` type.
 ///
 /// This function is called when the Lua script requires the module. It creates
 /// a table containing the module's public interface, which in this case is
 /// the `Vector.new` constructor.
-#[mlua::lua_module]
+#[cfg_attr(feature = "module", mlua::lua_module)]
 fn vector(lua: &Lua) -> LuaResult<LuaTable> {
     let module = lua.create_table()?;
 
     module.set("new", lua.create_function(LuaVectorAdapter::new)?)?;
     module.set("of", lua.create_function(LuaVectorAdapter::of)?)?;
+    module.set(
+        "frozen_of",
+        lua.create_function(LuaVectorAdapter::frozen_of)?,
+    )?;
+    module.set(
+        "from_table",
+        lua.create_function(LuaVectorAdapter::from_table)?,
+    )?;
+    module.set("range", lua.create_function(LuaVectorAdapter::range)?)?;
+    module.set("rep", lua.create_function(LuaVectorAdapter::rep)?)?;
+    module.set("generate", lua.create_function(LuaVectorAdapter::generate)?)?;
+    module.set(
+        "from_iter",
+        lua.create_function(LuaVectorAdapter::from_iter)?,
+    )?;
+    module.set(
+        "with_capacity",
+        lua.create_function(LuaVectorAdapter::with_capacity)?,
+    )?;
+    module.set(
+        "from_json",
+        lua.create_function(LuaVectorAdapter::from_json)?,
+    )?;
+    module.set(
+        "from_msgpack",
+        lua.create_function(LuaVectorAdapter::from_msgpack)?,
+    )?;
+
+    module.set("deque", lua.create_function(LuaDequeAdapter::new)?)?;
+    module.set("ring", lua.create_function(LuaRingAdapter::new)?)?;
+    module.set(
+        "persistent",
+        lua.create_function(LuaPersistentVectorAdapter::new)?,
+    )?;
+    module.set("bytes", lua.create_function(LuaBytesAdapter::new)?)?;
+    module.set(
+        "bytes_from_hex",
+        lua.create_function(LuaBytesAdapter::from_hex)?,
+    )?;
+    module.set(
+        "bytes_from_base64",
+        lua.create_function(LuaBytesAdapter::from_base64)?,
+    )?;
+    module.set("grid", lua.create_function(LuaGridAdapter::new)?)?;
+    module.set("identity", lua.create_function(LuaGridAdapter::identity)?)?;
+
+    register_typed::<f64>(lua, &module)?;
+    register_typed::<f32>(lua, &module)?;
+    register_typed::<i64>(lua, &module)?;
+    register_typed::<i32>(lua, &module)?;
 
     Ok(module)
 }
 
+/// Registers the module into a Lua state created by a Rust host.
+///
+/// Afterwards, `require("vector")` returns the module table, which is also returned by
+/// this function. Hosts should depend on this crate without the default `module`
+/// feature, enabling the feature of their Lua version instead.
+pub fn register(lua: &Lua) -> LuaResult<LuaTable> {
+    let module = vector(lua)?;
+    lua.register_module("vector", &module)?;
+
+    Ok(module)
+}
+
+/// Adds the constructors of the typed vector with elements `T` to the module table.
+///
+/// For an element type named `f64`, this registers `vector.f64(size, filler)` and
+/// `vector.f64_from(values)`.
+fn register_typed<T: Numeric>(lua: &Lua, module: &LuaTable) -> LuaResult<()> {
+    let new = lua.create_function(LuaTypedVectorAdapter::<T>::new)?;
+    let from = lua.create_function(LuaTypedVectorAdapter::<T>::from)?;
+
+    module.set(T::NAME, new)?;
+    module.set(format!("{}_from", T::NAME), from)?;
+
+    Ok(())
+}
+
+/// Registers the `read_*` and `write_*` methods of a byte buffer for the element type `T`.
+///
+/// Single bytes have no byte order, so they get plain `read_u8`-style names, while the
+/// other types get one reader and one writer per byte order, such as `read_u16le`.
+fn add_packed<T: Packable, M: LuaUserDataMethods<LuaBytesAdapter>>(methods: &mut M) {
+    let orders: &[Endian] = if T::SIZE == 1 {
+        &[Endian::Little]
+    } else {
+        &[Endian::Little, Endian::Big]
+    };
+
+    for &endian in orders {
+        let suffix = if T::SIZE == 1 { "" } else { endian.suffix() };
+
+        methods.add_method(
+            format!("read_{}{}", T::NAME, suffix),
+            move |_, this, (offset,): (LuaInteger,)| this.read::<T>(offset, endian),
+        );
+        methods.add_method_mut(
+            format!("write_{}{}", T::NAME, suffix),
+            move |_, this, (offset, value): (LuaInteger, LuaValue)| {
+                this.write::<T>(offset, &value, endian)
+            },
+        );
+    }
+}
+
+/// Wraps a mutating `Vector` method so that it causes a Lua error on a frozen vector,
+/// and notifies the observers of the vector like `observed`.
+fn mutator<A: FromLuaMulti, R>(
+    name: &'static str,
+    method: impl Fn(&Lua, &mut LuaVectorAdapter, A) -> LuaResult<R>,
+) -> impl Fn(&Lua, (LuaAnyUserData, A)) -> LuaResult<R> {
+    observed(name, move |lua, this, args| {
+        this.ensure_mutable(name)?;
+        method(lua, this, args)
+    })
+}
+
+/// Wraps a mutating `Vector` method so that the observers of the vector are notified of
+/// the changes it made once it returns.
+///
+/// The result is registered as a function receiving the userdata, so that the vector is
+/// no longer borrowed when the observers run. An error raised by the method takes
+/// precedence over an error raised by an observer.
+fn observed<A: FromLuaMulti, R>(
+    name: &'static str,
+    method: impl Fn(&Lua, &mut LuaVectorAdapter, A) -> LuaResult<R>,
+) -> impl Fn(&Lua, (LuaAnyUserData, A)) -> LuaResult<R> {
+    move |lua, (ud, args)| {
+        let (result, changes) = {
+            let mut this = ud.borrow_mut::<LuaVectorAdapter>()?;
+            let result = method(lua, &mut this, args);
+            (result, this.vec.finish_mutation())
+        };
+
+        let notified = observe_adapter::notify(&ud, name, changes);
+        let result = result?;
+        notified?;
+        Ok(result)
+    }
+}
+
 /// The implementation of the `mlua::LuaUserData` trait for `LuaVectorAdapter`.
 ///
 /// This block links the Rust methods to the Lua runtime, making them callable
@@ -28,10 +210,288 @@ impl LuaUserData for LuaVectorAdapter {
     fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
         methods.add_method("get", Self::get);
         methods.add_method("is_same", Self::is_same);
+        methods.add_method("map", Self::map);
+        methods.add_method("filter", Self::filter);
+        methods.add_method("reduce", Self::reduce);
+        methods.add_method("find", Self::find);
+        methods.add_method("any", Self::any);
+        methods.add_method("all", Self::all);
+        methods.add_method("for_each", Self::for_each);
+        methods.add_method("contains", Self::contains);
+        methods.add_method("index_of", Self::index_of);
+        methods.add_method("last_index_of", Self::last_index_of);
+        methods.add_method("count", Self::count);
+        methods.add_method("unique", Self::unique);
+        methods.add_method("chunks", Self::chunks);
+        methods.add_method("windows", Self::windows);
+        methods.add_method("group_by", Self::group_by);
+        methods.add_method("partition", Self::partition);
+        methods.add_method("zip", Self::zip);
+        methods.add_method("unzip", Self::unzip);
+        methods.add_method("is_frozen", Self::is_frozen);
+        methods.add_method("thaw_copy", Self::thaw_copy);
+        methods.add_method("snapshot", Self::snapshot);
+        methods.add_method("sorted", Self::sorted);
+        methods.add_method("binary_search", Self::binary_search);
+        methods.add_method("partition_point", Self::partition_point);
+        methods.add_method("slice", Self::slice);
+        methods.add_method("dot", Self::dot);
+        methods.add_method("norm", Self::norm);
+        methods.add_method("normalize", Self::normalize);
+        methods.add_method("cross", Self::cross);
+        methods.add_method("sum", Self::sum);
+        methods.add_method("min", Self::min);
+        methods.add_method("max", Self::max);
+        methods.add_method("mean", Self::mean);
+        methods.add_method("to_table", Self::to_table);
+        methods.add_method("unpack", Self::unpack);
+        methods.add_method("capacity", Self::capacity);
+        methods.add_method("to_persistent", LuaPersistentVectorAdapter::from_vector);
+
+        methods.add_function("view", LuaVectorViewAdapter::new);
+        methods.add_function("lazy", LuaLazyAdapter::new);
+        methods.add_function("extend", Self::extend);
+        methods.add_function("on_change", LuaSubscriptionAdapter::subscribe);
+        methods.add_function("batch", Self::batch);
+        methods.add_function("iter", Self::iter);
+        methods.add_function("iter_rev", Self::iter_rev);
+        methods.add_function("enumerate", Self::enumerate);
+        methods.add_function("deep_equals", Self::deep_equals);
+        methods.add_function("to_json", Self::to_json);
+        methods.add_function("to_msgpack", Self::to_msgpack);
+
+        // Mutators are wrapped by `mutator`, which rejects frozen vectors and notifies
+        // observers. `extend` is registered as a function and does both itself.
+        methods.add_method_mut("freeze", Self::freeze);
+        methods.add_method_mut("track_history", Self::track_history);
+        methods.add_function("set", mutator("set", Self::set));
+        methods.add_function("push", mutator("push", Self::push));
+        methods.add_function("pop", mutator("pop", Self::pop));
+        methods.add_function("sort", mutator("sort", Self::sort));
+        methods.add_function("sort_stable", mutator("sort_stable", Self::sort_stable));
+        methods.add_function("insert", mutator("insert", Self::insert));
+        methods.add_function("remove", mutator("remove", Self::remove));
+        methods.add_function("swap_remove", mutator("swap_remove", Self::swap_remove));
+        methods.add_function("splice", mutator("splice", Self::splice));
+        methods.add_function("truncate", mutator("truncate", Self::truncate));
+        methods.add_function("clear", mutator("clear", Self::clear));
+        methods.add_function("reserve", mutator("reserve", Self::reserve));
+        methods.add_function(
+            "shrink_to_fit",
+            mutator("shrink_to_fit", Self::shrink_to_fit),
+        );
+        methods.add_function("swap", mutator("swap", Self::swap));
+        methods.add_function("reverse", mutator("reverse", Self::reverse));
+        methods.add_function("rotate", mutator("rotate", Self::rotate));
+        methods.add_function("dedup", mutator("dedup", Self::dedup));
+        methods.add_function("undo", mutator("undo", Self::undo));
+        methods.add_function("redo", mutator("redo", Self::redo));
+        methods.add_function("restore", mutator("restore", Self::restore));
+
+        methods.add_meta_method("__index", Self::index);
+        methods.add_meta_method("__len", Self::len);
+        methods.add_meta_method("__eq", Self::equals);
+        methods.add_meta_method("__call", Self::call);
+        methods.add_meta_method("__unm", Self::unm);
+
+        methods.add_meta_function("__add", Self::add);
+        methods.add_meta_function("__sub", Self::sub);
+        methods.add_meta_function("__mul", Self::mul);
+        methods.add_meta_function("__div", Self::div);
+        methods.add_meta_function("__lt", Self::lt);
+        methods.add_meta_function("__le", Self::le);
+        methods.add_meta_function("__concat", Self::concat);
+        methods.add_meta_function("__tostring", Self::to_string);
+
+        // `pairs` honours `__pairs` since Lua 5.2, and `ipairs` honours `__ipairs` in
+        // Lua 5.2 and 5.3 only. Luau uses `__iter` for its generalized iteration.
+        #[cfg(any(feature = "lua52", feature = "lua53", feature = "lua54"))]
+        methods.add_meta_function("__pairs", Self::pairs);
+        #[cfg(any(feature = "lua52", feature = "lua53"))]
+        methods.add_meta_function("__ipairs", Self::pairs);
+        #[cfg(feature = "luau")]
+        methods.add_meta_function("__iter", Self::pairs);
+
+        methods.add_meta_function("__newindex", mutator("__newindex", Self::newindex));
+
+        #[cfg(feature = "lua54")]
+        methods.add_meta_method_mut("__close", Self::close);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaVectorViewAdapter`.
+///
+/// Views expose the same indexing surface as `Vector`, so code that only reads and
+/// writes elements can receive either of them.
+impl LuaUserData for LuaVectorViewAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("get", Self::get);
+        methods.add_method("set", Self::set);
+        methods.add_method("to_vector", Self::to_vector);
+
+        methods.add_meta_method("__index", Self::index);
+        methods.add_meta_method("__len", Self::len);
+        methods.add_meta_method("__newindex", Self::newindex);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaTypedVectorAdapter`.
+///
+/// Typed vectors share the element access surface of `Vector`, so numeric code can
+/// switch between both representations without changes.
+impl<T: Numeric> LuaUserData for LuaTypedVectorAdapter<T> {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("get", Self::get);
+        methods.add_method("is_same", Self::is_same);
+        methods.add_method("to_vector", Self::to_vector);
+        methods.add_method("element_type", Self::element_type);
+
+        methods.add_method_mut("set", Self::set);
+        methods.add_method_mut("push", Self::push);
+        methods.add_method_mut("pop", Self::pop);
+
+        methods.add_meta_method("__index", Self::index);
+        methods.add_meta_method("__len", Self::len);
+        methods.add_meta_method("__eq", Self::equals);
+
+        methods.add_meta_method_mut("__newindex", Self::newindex);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaDequeAdapter`.
+///
+/// Deques share the element access surface of `Vector`, and add the operations at the
+/// front of the queue.
+impl LuaUserData for LuaDequeAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("get", Self::get);
+        methods.add_method("is_same", Self::is_same);
+        methods.add_method("peek_front", Self::peek_front);
+        methods.add_method("peek_back", Self::peek_back);
+        methods.add_method("to_vector", Self::to_vector);
 
         methods.add_method_mut("set", Self::set);
         methods.add_method_mut("push", Self::push);
+        methods.add_method_mut("push_front", Self::push_front);
         methods.add_method_mut("pop", Self::pop);
+        methods.add_method_mut("pop_front", Self::pop_front);
+
+        methods.add_meta_method("__index", Self::index);
+        methods.add_meta_method("__len", Self::len);
+        methods.add_meta_method("__eq", Self::equals);
+
+        methods.add_meta_method_mut("__newindex", Self::newindex);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaRingAdapter`.
+///
+/// Ring buffers share the element access surface of `Vector`, with indices relative
+/// to the oldest value.
+impl LuaUserData for LuaRingAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("get", Self::get);
+        methods.add_method("is_full", Self::is_full);
+        methods.add_method("capacity", Self::capacity);
+        methods.add_method("to_vector", Self::to_vector);
+
+        methods.add_function("iter", Self::iter);
+
+        methods.add_method_mut("set", Self::set);
+        methods.add_method_mut("push", Self::push);
+        methods.add_method_mut("clear", Self::clear);
+
+        methods.add_meta_method("__index", Self::index);
+        methods.add_meta_method("__len", Self::len);
+
+        methods.add_meta_method_mut("__newindex", Self::newindex);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaPersistentVectorAdapter`.
+///
+/// Persistent vectors share the read surface of `Vector`, and every update returns a
+/// new version, so there are no mutating methods.
+impl LuaUserData for LuaPersistentVectorAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("get", Self::get);
+        methods.add_method("push", Self::push);
+        methods.add_method("set", Self::set);
+        methods.add_method("to_vector", Self::to_vector);
+
+        methods.add_function("pop", Self::pop);
+
+        methods.add_meta_method("__index", Self::index);
+        methods.add_meta_method("__len", Self::len);
+        methods.add_meta_method("__eq", Self::equals);
+        methods.add_meta_method("__newindex", Self::newindex);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaLazyAdapter`.
+///
+/// Every method consumes the iterator, so they are all registered as mutable methods.
+impl LuaUserData for LuaLazyAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method_mut("map", Self::map);
+        methods.add_method_mut("filter", Self::filter);
+        methods.add_method_mut("take", Self::take_n);
+        methods.add_method_mut("skip", Self::skip);
+        methods.add_method_mut("take_while", Self::take_while);
+        methods.add_method_mut("skip_while", Self::skip_while);
+        methods.add_method_mut("zip", Self::zip);
+        methods.add_method_mut("chain", Self::chain);
+        methods.add_method_mut("enumerate", Self::enumerate);
+        methods.add_method_mut("step_by", Self::step_by);
+
+        methods.add_method_mut("collect", Self::collect);
+        methods.add_method_mut("fold", Self::fold);
+        methods.add_method_mut("count", Self::count);
+        methods.add_method_mut("first", Self::first);
+        methods.add_method_mut("last", Self::last);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaGridAdapter`.
+impl LuaUserData for LuaGridAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("get", Self::get);
+        methods.add_method("rows", Self::rows);
+        methods.add_method("cols", Self::cols);
+        methods.add_method("row", Self::row);
+        methods.add_method("column", Self::column);
+        methods.add_method("transpose", Self::transpose);
+        methods.add_method("neighbours", Self::neighbours);
+        methods.add_method("matmul", Self::matmul);
+
+        methods.add_method_mut("set", Self::set);
+        methods.add_method_mut("flood_fill", Self::flood_fill);
+    }
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaBytesAdapter`.
+///
+/// Byte buffers share the element access surface of `Vector`, and add the string
+/// conversions and the binary readers and writers for every packable type.
+impl LuaUserData for LuaBytesAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("get", Self::get);
+        methods.add_method("to_string", Self::to_string);
+        methods.add_method("to_hex", Self::to_hex);
+        methods.add_method("to_base64", Self::to_base64);
+
+        methods.add_method_mut("set", Self::set);
+        methods.add_method_mut("push", Self::push);
+
+        add_packed::<u8, M>(methods);
+        add_packed::<i8, M>(methods);
+        add_packed::<u16, M>(methods);
+        add_packed::<i16, M>(methods);
+        add_packed::<u32, M>(methods);
+        add_packed::<i32, M>(methods);
+        add_packed::<i64, M>(methods);
+        add_packed::<f32, M>(methods);
+        add_packed::<f64, M>(methods);
 
         methods.add_meta_method("__index", Self::index);
         methods.add_meta_method("__len", Self::len);
@@ -39,4 +499,25 @@ impl LuaUserData for LuaVectorAdapter {
 
         methods.add_meta_method_mut("__newindex", Self::newindex);
     }
-}
\ No newline at end of file
+}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaSubscriptionAdapter`.
+impl LuaUserData for LuaSubscriptionAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("unsubscribe", Self::unsubscribe);
+        methods.add_function("is_active", Self::is_active);
+    }
+}
+
+/// The observers of a `Vector` are stored as a userdata attached to it, which is never
+/// handed out to Lua code, so it exposes no methods.
+impl LuaUserData for LuaObservers {}
+
+/// The implementation of the `mlua::LuaUserData` trait for `LuaSnapshotAdapter`.
+impl LuaUserData for LuaSnapshotAdapter {
+    fn add_methods<M: LuaUserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("to_vector", Self::to_vector);
+
+        methods.add_meta_method("__len", Self::len);
+    }
+}
@@ -0,0 +1,299 @@
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, as_float};
+
+/// An element-wise arithmetic operation supported by numeric vectors.
+#[derive(Clone, Copy)]
+pub(crate) enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl ArithOp {
+    /// The verb used to describe the operation in error messages.
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Sub => "subtract",
+            Self::Mul => "multiply",
+            Self::Div => "divide",
+        }
+    }
+
+    /// Applies the operation to two Lua numbers.
+    ///
+    /// On Lua 5.3 and 5.4, two integers produce an integer (wrapping on overflow) except
+    /// for division, which always produces a float. Other versions have no integer subtype,
+    /// so every operation is computed on floats like the host would. Returns `None` if an
+    /// operand is not a number.
+    fn apply(self, a: &LuaValue, b: &LuaValue) -> Option<LuaValue> {
+        #[cfg(any(feature = "lua53", feature = "lua54"))]
+        if let (LuaValue::Integer(x), LuaValue::Integer(y)) = (a, b) {
+            let int = match self {
+                Self::Add => Some(x.wrapping_add(*y)),
+                Self::Sub => Some(x.wrapping_sub(*y)),
+                Self::Mul => Some(x.wrapping_mul(*y)),
+                Self::Div => None,
+            };
+            if let Some(int) = int {
+                return Some(LuaValue::Integer(int));
+            }
+        }
+
+        let (x, y) = (as_float(a)?, as_float(b)?);
+        let num = match self {
+            Self::Add => x + y,
+            Self::Sub => x - y,
+            Self::Mul => x * y,
+            Self::Div => x / y,
+        };
+        Some(LuaValue::Number(num))
+    }
+}
+
+/// The side of a binary operation on which a broadcast scalar appears.
+#[derive(Clone, Copy)]
+pub(crate) enum Side {
+    /// The scalar is the left operand, as in `2 * vec`.
+    Left,
+    /// The scalar is the right operand, as in `vec * 2`.
+    Right,
+}
+
+/// Numeric operations over vectors whose elements are all Lua numbers.
+///
+/// Every operation reports, through an `Err`, the first element that is not a number,
+/// as well as length mismatches between vectors.
+impl LuaVector {
+    /// Combines two vectors of equal length element by element.
+    pub(crate) fn zip_with(&self, other: &Self, op: ArithOp) -> Result<Self, String> {
+        self.zip_described(other, op, op.verb())
+    }
+
+    /// Combines every element with a scalar, placed on the given side of the operation.
+    pub(crate) fn broadcast(
+        &self,
+        op: ArithOp,
+        scalar: &LuaValue,
+        side: Side,
+    ) -> Result<Self, String> {
+        if as_float(scalar).is_none() {
+            return Err(format!(
+                "Cannot {} a Vector and a {}.",
+                op.verb(),
+                scalar.type_name()
+            ));
+        }
+
+        let values = self
+            .as_slice()
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let result = match side {
+                    Side::Left => op.apply(scalar, value),
+                    Side::Right => op.apply(value, scalar),
+                };
+                result.ok_or_else(|| operand_error(op.verb(), idx, value))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::of(values))
+    }
+
+    /// Negates every element.
+    ///
+    /// Floats are negated rather than subtracted from zero, so that `-0.0` and `0.0`
+    /// swap signs. Integers keep their subtype on Lua 5.3 and 5.4, wrapping on overflow.
+    pub(crate) fn negate(&self) -> Result<Self, String> {
+        self.ensure_numbers("negate")?;
+
+        let values = self
+            .as_slice()
+            .iter()
+            .map(|value| match value {
+                #[cfg(any(feature = "lua53", feature = "lua54"))]
+                LuaValue::Integer(int) => LuaValue::Integer(int.wrapping_neg()),
+                other => LuaValue::Number(-as_float(other).unwrap_or_default()),
+            })
+            .collect();
+        Ok(Self::of(values))
+    }
+
+    /// Adds up every element. An empty vector sums to the integer `0`.
+    pub(crate) fn sum(&self) -> Result<LuaValue, String> {
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .try_fold(LuaValue::Integer(0), |acc, (idx, value)| {
+                ArithOp::Add
+                    .apply(&acc, value)
+                    .ok_or_else(|| operand_error("sum", idx, value))
+            })
+    }
+
+    /// Computes the dot product with another vector of equal length.
+    pub(crate) fn dot(&self, other: &Self) -> Result<LuaValue, String> {
+        self.zip_described(other, ArithOp::Mul, "compute the dot product of")?
+            .sum()
+    }
+
+    /// Computes the Euclidean norm (length) of the vector.
+    ///
+    /// The squares are accumulated as floats, so that large integers cannot wrap around.
+    pub(crate) fn norm(&self) -> Result<f64, String> {
+        self.ensure_numbers("compute the norm of")?;
+
+        Ok(self.floats().map(|num| num * num).sum::<f64>().sqrt())
+    }
+
+    /// Creates a new vector with the same direction and a norm of `1`.
+    ///
+    /// Returns an `Err` for a vector whose norm is zero.
+    pub(crate) fn normalize(&self) -> Result<Self, String> {
+        let norm = self.norm()?;
+        if norm == 0.0 {
+            return Err("Cannot normalize a Vector with zero norm.".to_string());
+        }
+
+        self.broadcast(ArithOp::Div, &LuaValue::Number(norm), Side::Right)
+    }
+
+    /// Computes the cross product with another vector. Both vectors must have length 3.
+    pub(crate) fn cross(&self, other: &Self) -> Result<Self, String> {
+        if self.len() != 3 || other.len() != 3 {
+            return Err(format!(
+                "Cross product requires two Vectors of length 3, got {} and {}.",
+                self.len(),
+                other.len()
+            ));
+        }
+        self.ensure_numbers("compute the cross product of")?;
+        other.ensure_numbers("compute the cross product of")?;
+
+        let (a, b) = (self.as_slice(), other.as_slice());
+        let component = |i: usize, j: usize| {
+            let lhs = ArithOp::Mul.apply(&a[i], &b[j])?;
+            let rhs = ArithOp::Mul.apply(&a[j], &b[i])?;
+            ArithOp::Sub.apply(&lhs, &rhs)
+        };
+
+        let values = [(1, 2), (2, 0), (0, 1)]
+            .into_iter()
+            .map(|(i, j)| component(i, j).unwrap_or(LuaNil))
+            .collect();
+        Ok(Self::of(values))
+    }
+
+    /// Returns the smallest element, or `None` for an empty vector.
+    pub(crate) fn min(&self) -> Result<Option<LuaValue>, String> {
+        self.extreme("compute the minimum of", |candidate, best| candidate < best)
+    }
+
+    /// Returns the largest element, or `None` for an empty vector.
+    pub(crate) fn max(&self) -> Result<Option<LuaValue>, String> {
+        self.extreme("compute the maximum of", |candidate, best| candidate > best)
+    }
+
+    /// Returns the arithmetic mean of the elements, or `None` for an empty vector.
+    ///
+    /// The elements are accumulated as floats, so that large integers cannot wrap around.
+    pub(crate) fn mean(&self) -> Result<Option<f64>, String> {
+        self.ensure_numbers("compute the mean of")?;
+        if self.len() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(self.floats().sum::<f64>() / self.len() as f64))
+    }
+
+    /// Returns the first element for which `better(candidate, best)` holds against all others.
+    fn extreme(
+        &self,
+        verb: &str,
+        better: impl Fn(f64, f64) -> bool,
+    ) -> Result<Option<LuaValue>, String> {
+        self.ensure_numbers(verb)?;
+
+        let mut best: Option<(&LuaValue, f64)> = None;
+        for value in self.as_slice() {
+            let num = as_float(value).unwrap_or_default();
+            match best {
+                Some((_, best_num)) if !better(num, best_num) => {}
+                _ => best = Some((value, num)),
+            }
+        }
+        Ok(best.map(|(value, _)| value.clone()))
+    }
+
+    /// Combines two vectors of equal length, using `verb` to describe the operation in errors.
+    fn zip_described(&self, other: &Self, op: ArithOp, verb: &str) -> Result<Self, String> {
+        self.ensure_same_len(other, verb)?;
+        self.ensure_numbers(verb)?;
+        other.ensure_numbers(verb)?;
+
+        let values = self
+            .as_slice()
+            .iter()
+            .zip(other.as_slice())
+            .map(|(a, b)| op.apply(a, b).unwrap_or(LuaNil))
+            .collect();
+
+        Ok(Self::of(values))
+    }
+
+    /// Returns the elements as floats, reading anything that is not a number as `0.0`.
+    fn floats(&self) -> impl Iterator<Item = f64> + '_ {
+        self.as_slice()
+            .iter()
+            .map(|value| as_float(value).unwrap_or_default())
+    }
+
+    /// Returns an `Err` if the two vectors have different lengths.
+    fn ensure_same_len(&self, other: &Self, verb: &str) -> Result<(), String> {
+        if self.len() != other.len() {
+            return Err(format!(
+                "Cannot {} Vectors of different lengths ({} and {}).",
+                verb,
+                self.len(),
+                other.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns an `Err` describing the first element that is not a number, if any.
+    fn ensure_numbers(&self, verb: &str) -> Result<(), String> {
+        if self
+            .as_slice()
+            .iter()
+            .all(|value| as_float(value).is_some())
+        {
+            Ok(())
+        } else {
+            Err(self.first_non_number(verb))
+        }
+    }
+
+    /// Builds the error message for the first element that is not a number.
+    fn first_non_number(&self, verb: &str) -> String {
+        self.as_slice()
+            .iter()
+            .enumerate()
+            .find(|(_, value)| as_float(value).is_none())
+            .map(|(idx, value)| operand_error(verb, idx, value))
+            .unwrap_or_default()
+    }
+}
+
+/// Builds the error message for a non-numeric element at the Rust 0-based index `idx`.
+fn operand_error(verb: &str, idx: usize, value: &LuaValue) -> String {
+    format!(
+        "Cannot {}: element at index {} is a {}, expected a number.",
+        verb,
+        idx + 1,
+        value.type_name()
+    )
+}
@@ -0,0 +1,99 @@
+use mlua::prelude::*;
+
+/// A change made to a `LuaVector`, recorded while the vector has observers.
+#[derive(Clone)]
+pub(crate) enum Change {
+    /// A single element was replaced, added or removed at a Lua 1-based index.
+    ///
+    /// `old` is `None` for an added element, and `new` is `None` for a removed one.
+    Element {
+        index: LuaInteger,
+        old: Option<LuaValue>,
+        new: Option<LuaValue>,
+    },
+    /// Several elements changed at once, as in `sort` or `clear`.
+    Bulk,
+}
+
+/// The observers of a vector and the state of its pending batches.
+///
+/// Subscriptions are identified by the id returned when subscribing, and are kept in
+/// subscription order. The handlers themselves are not held here but in a Lua table
+/// attached to the vector, so that the garbage collector can trace them. While a batch
+/// is open, changes are not dispatched but only remembered, so that a single
+/// notification can be sent when the outermost batch ends.
+#[derive(Default)]
+pub(crate) struct LuaObservers {
+    /// The ids of the active subscriptions.
+    ids: Vec<u64>,
+    /// The id of the next subscription.
+    next_id: u64,
+    /// The number of nested batches currently open.
+    batch_depth: usize,
+    /// Whether a change happened since the outermost batch was opened.
+    batched_changes: bool,
+}
+
+impl LuaObservers {
+    /// Adds a subscription, returning its id.
+    pub(crate) fn subscribe(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.ids.push(id);
+        id
+    }
+
+    /// Removes a subscription.
+    ///
+    /// Returns `false` if the subscription was already cancelled.
+    pub(crate) fn unsubscribe(&mut self, id: u64) -> bool {
+        let len = self.ids.len();
+        self.ids.retain(|subscribed| *subscribed != id);
+        self.ids.len() != len
+    }
+
+    /// Returns `true` if the subscription is still active.
+    pub(crate) fn is_subscribed(&self, id: u64) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Returns `true` if no handler is subscribed.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns a copy of the subscription ids, so the handlers can be called while
+    /// changes are made.
+    pub(crate) fn ids(&self) -> Vec<u64> {
+        self.ids.clone()
+    }
+
+    /// Opens a batch, possibly nested in another one.
+    pub(crate) fn begin_batch(&mut self) {
+        self.batch_depth += 1;
+    }
+
+    /// Closes a batch.
+    ///
+    /// Returns `true` if it was the outermost one and changes happened meanwhile, in
+    /// which case the handlers should be notified once.
+    pub(crate) fn end_batch(&mut self) -> bool {
+        self.batch_depth = self.batch_depth.saturating_sub(1);
+        if self.batch_depth > 0 {
+            return false;
+        }
+        std::mem::take(&mut self.batched_changes)
+    }
+
+    /// Remembers changes made during a batch.
+    ///
+    /// Returns `false` if no batch is open, in which case the changes should be
+    /// dispatched right away.
+    pub(crate) fn defer(&mut self) -> bool {
+        if self.batch_depth == 0 {
+            return false;
+        }
+        self.batched_changes = true;
+        true
+    }
+}
@@ -0,0 +1,200 @@
+use mlua::prelude::*;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::history_adapter;
+use crate::observe::{Change, LuaObservers};
+
+/// A type alias for the `Subscription` struct, used for clarity in Lua bindings.
+pub(super) type LuaSubscriptionAdapter = Subscription;
+
+/// The name of the user value holding the `LuaObservers` of a `Vector` userdata.
+///
+/// Observers live next to the vector rather than inside it, so that they can be called
+/// after the vector is no longer borrowed, and so that copies of a vector do not
+/// inherit them.
+const OBSERVERS: &str = "observers";
+
+/// The name of the user value holding the handlers of a `Vector` userdata, keyed by the
+/// id of their subscription.
+///
+/// They are kept in a Lua table rather than in `LuaObservers`, so that the garbage
+/// collector can trace them: a handler capturing its own vector does not keep it alive.
+const HANDLERS: &str = "handlers";
+
+/// The handle returned by `Vector:on_change`, used to cancel the subscription.
+///
+/// The observed `Vector` userdata is held as the user value of the handle, for the same
+/// reason the handlers are held in a Lua table.
+pub(super) struct Subscription {
+    /// The id of the subscription in the observers of the vector.
+    id: u64,
+}
+
+impl LuaSubscriptionAdapter {
+    /// The Lua-facing `Vector:on_change(func)` method.
+    ///
+    /// Subscribes `func(op, index, old, new)` to every change made to the vector, and
+    /// returns the subscription handle. It is registered as a function because the
+    /// handle needs to hold the vector userdata itself.
+    pub(super) fn subscribe(
+        lua: &Lua,
+        (vec, func): (LuaAnyUserData, LuaFunction),
+    ) -> LuaResult<LuaAnyUserData> {
+        let observers = attach_observers(lua, &vec)?;
+        let id = observers.borrow_mut::<LuaObservers>()?.subscribe();
+        handlers_of(&vec)?.raw_set(id, func)?;
+        vec.borrow_mut::<LuaVectorAdapter>()?
+            .vec
+            .record_changes(true);
+
+        let subscription = lua.create_userdata(Self { id })?;
+        subscription.set_user_value(&vec)?;
+        Ok(subscription)
+    }
+
+    /// The Lua-facing `unsubscribe` method.
+    ///
+    /// Stops the notifications of this subscription. Returns `false` if it was already
+    /// cancelled.
+    pub(super) fn unsubscribe(_: &Lua, this: LuaAnyUserData) -> LuaResult<bool> {
+        let id = this.borrow::<Self>()?.id;
+        let vec = this.user_value::<LuaAnyUserData>()?;
+        let Some(observers) = observers_of(&vec)? else {
+            return Ok(false);
+        };
+        let mut observers = observers.borrow_mut::<LuaObservers>()?;
+
+        let removed = observers.unsubscribe(id);
+        handlers_of(&vec)?.raw_set(id, LuaNil)?;
+        if observers.is_empty() {
+            drop(observers);
+            let mut vec = vec.borrow_mut::<LuaVectorAdapter>()?;
+            vec.vec.record_changes(false);
+        }
+        Ok(removed)
+    }
+
+    /// The Lua-facing `is_active` method, returning `false` once unsubscribed.
+    pub(super) fn is_active(_: &Lua, this: LuaAnyUserData) -> LuaResult<bool> {
+        let id = this.borrow::<Self>()?.id;
+        let vec = this.user_value::<LuaAnyUserData>()?;
+        match observers_of(&vec)? {
+            Some(observers) => Ok(observers.borrow::<LuaObservers>()?.is_subscribed(id)),
+            None => Ok(false),
+        }
+    }
+}
+
+impl LuaVectorAdapter {
+    /// The Lua-facing `batch` method.
+    ///
+    /// Calls `func(vec)`, holding back the notifications of the changes it makes. Once
+    /// the outermost batch ends, the observers are notified a single time with the
+    /// `"batch"` operation, even if `func` raised an error, which is then propagated.
+    /// If the history is tracked, the changes also form a single undo step.
+    pub(super) fn batch(
+        _: &Lua,
+        (vec, func): (LuaAnyUserData, LuaFunction),
+    ) -> LuaResult<LuaMultiValue> {
+        history_adapter::begin_group(&vec)?;
+        let Some(observers) = observers_of(&vec)? else {
+            let result = func.call(&vec);
+            history_adapter::end_group(&vec)?;
+            return result;
+        };
+
+        observers.borrow_mut::<LuaObservers>()?.begin_batch();
+        let result = func.call::<LuaMultiValue>(&vec);
+        let changed = observers.borrow_mut::<LuaObservers>()?.end_batch();
+        history_adapter::end_group(&vec)?;
+
+        if changed {
+            dispatch(&vec, &observers, "batch", &[Change::Bulk])?;
+        }
+        result
+    }
+}
+
+/// Notifies the observers of a `Vector` userdata of the changes made by `op`.
+///
+/// It must be called once the vector is no longer borrowed, so that the observers can
+/// read it. Every observer is called even if one fails, and the first error is then
+/// reported: the changes are already applied, so the vector itself stays consistent.
+pub(super) fn notify(vec: &LuaAnyUserData, op: &str, changes: Vec<Change>) -> LuaResult<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let Some(observers) = observers_of(vec)? else {
+        return Ok(());
+    };
+    if observers.borrow_mut::<LuaObservers>()?.defer() {
+        return Ok(());
+    }
+
+    dispatch(vec, &observers, op, &changes)
+}
+
+/// Calls every observer once per change, reporting the first error.
+fn dispatch(
+    vec: &LuaAnyUserData,
+    observers: &LuaAnyUserData,
+    op: &str,
+    changes: &[Change],
+) -> LuaResult<()> {
+    let table = handlers_of(vec)?;
+    let handlers = observers
+        .borrow::<LuaObservers>()?
+        .ids()
+        .into_iter()
+        .map(|id| table.raw_get::<LuaFunction>(id))
+        .collect::<LuaResult<Vec<_>>>()?;
+    let mut first_error = None;
+
+    for change in changes {
+        let (index, old, new) = match change {
+            Change::Element { index, old, new } => (
+                LuaValue::Integer(*index),
+                old.clone().unwrap_or(LuaNil),
+                new.clone().unwrap_or(LuaNil),
+            ),
+            Change::Bulk => (LuaNil, LuaNil, LuaNil),
+        };
+
+        for handler in &handlers {
+            let result = handler.call::<()>((op, index.clone(), old.clone(), new.clone()));
+            if let Err(err) = result {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    match first_error {
+        Some(err) => {
+            let msg = format!("on_change observer failed after {}: {}", op, err);
+            Err(LuaError::runtime(msg))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns the observers of a `Vector` userdata, or `None` if none was ever attached.
+fn observers_of(vec: &LuaAnyUserData) -> LuaResult<Option<LuaAnyUserData>> {
+    vec.named_user_value(OBSERVERS)
+}
+
+/// Returns the observers of a `Vector` userdata, attaching them on first use.
+fn attach_observers(lua: &Lua, vec: &LuaAnyUserData) -> LuaResult<LuaAnyUserData> {
+    if let Some(observers) = observers_of(vec)? {
+        return Ok(observers);
+    }
+
+    let observers = lua.create_userdata(LuaObservers::default())?;
+    vec.set_named_user_value(OBSERVERS, &observers)?;
+    vec.set_named_user_value(HANDLERS, lua.create_table()?)?;
+    Ok(observers)
+}
+
+/// Returns the table of handlers of a `Vector` userdata, attached with its observers.
+fn handlers_of(vec: &LuaAnyUserData) -> LuaResult<LuaTable> {
+    vec.named_user_value(HANDLERS)
+}
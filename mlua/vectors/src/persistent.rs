@@ -0,0 +1,238 @@
+use std::rc::Rc;
+
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, zero_based_index_in};
+
+/// The number of index bits consumed by every level of the trie.
+const BITS: u32 = 5;
+
+/// The number of children of a branch, or values of a leaf, when full.
+const WIDTH: usize = 1 << BITS;
+
+/// The mask selecting the index bits of a single level.
+const MASK: usize = WIDTH - 1;
+
+/// A node of the trie backing a `LuaPersistentVector`.
+///
+/// Nodes are never modified once shared: every update copies the nodes on the path from
+/// the root to the updated leaf, and shares all the others with the previous version.
+enum Node {
+    /// An inner node, holding up to `WIDTH` children.
+    Branch(Vec<Rc<Node>>),
+    /// A bottom node, holding up to `WIDTH` values.
+    Leaf(Vec<LuaValue>),
+}
+
+/// The core implementation of a persistent vector, a bit-partitioned trie.
+///
+/// Every update (`push`, `set`, `pop`) returns a new version in `O(log n)` and leaves
+/// the original untouched, while both versions share the unchanged parts of the trie.
+/// Cloning a version is `O(1)`. Indices follow the `LuaVector` rules.
+#[derive(Clone)]
+pub(crate) struct LuaPersistentVector {
+    /// The root node of the trie, a `Leaf` when `shift` is `0`.
+    root: Rc<Node>,
+    /// The number of values in the vector.
+    len: usize,
+    /// The number of index bits below the root level, a multiple of `BITS`.
+    shift: u32,
+}
+
+impl PartialEq for LuaPersistentVector {
+    fn eq(&self, other: &Self) -> bool {
+        if Rc::ptr_eq(&self.root, &other.root) {
+            return self.len == other.len;
+        }
+        self.len == other.len && (0..self.len).all(|idx| self.value(idx) == other.value(idx))
+    }
+}
+
+impl LuaPersistentVector {
+    /// The maximum length of the vector, constrained by the Lua `integer` type.
+    const MAX_LEN: usize = LuaInteger::MAX as usize;
+
+    /// Creates a new `LuaPersistentVector` holding the given values.
+    pub(crate) fn of(values: Vec<LuaValue>) -> Self {
+        let empty = Self {
+            root: Rc::new(Node::Leaf(Vec::new())),
+            len: 0,
+            shift: 0,
+        };
+
+        values
+            .into_iter()
+            .fold(empty, |vec, value| vec.push(value).unwrap_or(vec))
+    }
+
+    /// Retrieves a value based on a Lua 1-based index, following the `LuaVector` rules.
+    pub(crate) fn get(&self, index: LuaInteger) -> Option<LuaValue> {
+        zero_based_index_in(self.len, index).map(|idx| self.value(idx).clone())
+    }
+
+    /// Returns the number of elements in the vector.
+    pub(crate) fn len(&self) -> LuaInteger {
+        self.len as LuaInteger
+    }
+
+    /// Returns a new version with `value` appended to the end.
+    ///
+    /// Returns `None` if the vector has reached its maximum capacity.
+    pub(crate) fn push(&self, value: LuaValue) -> Option<Self> {
+        if self.len >= Self::MAX_LEN {
+            return None;
+        }
+
+        // The trie is full at its current depth: grow a new root above the old one.
+        if self.len == capacity(self.shift) {
+            let shift = self.shift + BITS;
+            let root = Node::Branch(vec![self.root.clone(), new_path(self.shift, value)]);
+            return Some(Self {
+                root: Rc::new(root),
+                len: self.len + 1,
+                shift,
+            });
+        }
+
+        Some(Self {
+            root: push_in(&self.root, self.shift, self.len, value),
+            len: self.len + 1,
+            shift: self.shift,
+        })
+    }
+
+    /// Returns a new version with the value at a given Lua index replaced.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub(crate) fn set(&self, index: LuaInteger, value: LuaValue) -> Option<Self> {
+        let idx = zero_based_index_in(self.len, index)?;
+
+        Some(Self {
+            root: set_in(&self.root, self.shift, idx, value),
+            len: self.len,
+            shift: self.shift,
+        })
+    }
+
+    /// Returns a new version without the last element, along with that element.
+    ///
+    /// Returns `None` if the vector is empty.
+    pub(crate) fn pop(&self) -> Option<(Self, LuaValue)> {
+        let last = self.len.checked_sub(1)?;
+        let value = self.value(last).clone();
+
+        let (mut root, mut shift) = match pop_in(&self.root, self.shift, last) {
+            Some(root) => (root, self.shift),
+            None => (Rc::new(Node::Leaf(Vec::new())), 0),
+        };
+        // Drop the levels left with a single child, keeping the trie as shallow as possible.
+        while let Node::Branch(children) = &*root
+            && children.len() == 1
+        {
+            root = children[0].clone();
+            shift -= BITS;
+        }
+
+        let vec = Self {
+            root,
+            len: last,
+            shift,
+        };
+        Some((vec, value))
+    }
+
+    /// Creates a mutable `LuaVector` holding the same values.
+    pub(crate) fn to_vector(&self) -> LuaVector {
+        LuaVector::of((0..self.len).map(|idx| self.value(idx).clone()).collect())
+    }
+
+    /// Returns the value at a Rust 0-based index, which must be in bounds.
+    fn value(&self, idx: usize) -> &LuaValue {
+        let mut node = &*self.root;
+        let mut shift = self.shift;
+
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    node = &children[(idx >> shift) & MASK];
+                    shift -= BITS;
+                }
+                Node::Leaf(values) => return &values[idx & MASK],
+            }
+        }
+    }
+}
+
+/// Returns the number of values a trie holds when full, given the shift of its root.
+fn capacity(shift: u32) -> usize {
+    1usize.checked_shl(shift + BITS).unwrap_or(usize::MAX)
+}
+
+/// Creates the chain of nodes leading from a node at `shift` down to a leaf with `value`.
+fn new_path(shift: u32, value: LuaValue) -> Rc<Node> {
+    if shift == 0 {
+        return Rc::new(Node::Leaf(vec![value]));
+    }
+    Rc::new(Node::Branch(vec![new_path(shift - BITS, value)]))
+}
+
+/// Returns a copy of `node` with `value` appended at the Rust 0-based index `idx`.
+fn push_in(node: &Node, shift: u32, idx: usize, value: LuaValue) -> Rc<Node> {
+    match node {
+        Node::Branch(children) => {
+            let mut children = children.clone();
+            let sub = (idx >> shift) & MASK;
+            match children.get(sub) {
+                Some(child) => children[sub] = push_in(child, shift - BITS, idx, value),
+                None => children.push(new_path(shift - BITS, value)),
+            }
+            Rc::new(Node::Branch(children))
+        }
+        Node::Leaf(values) => {
+            let mut values = values.clone();
+            values.push(value);
+            Rc::new(Node::Leaf(values))
+        }
+    }
+}
+
+/// Returns a copy of `node` with the value at the Rust 0-based index `idx` replaced.
+fn set_in(node: &Node, shift: u32, idx: usize, value: LuaValue) -> Rc<Node> {
+    match node {
+        Node::Branch(children) => {
+            let mut children = children.clone();
+            let sub = (idx >> shift) & MASK;
+            children[sub] = set_in(&children[sub], shift - BITS, idx, value);
+            Rc::new(Node::Branch(children))
+        }
+        Node::Leaf(values) => {
+            let mut values = values.clone();
+            values[idx & MASK] = value;
+            Rc::new(Node::Leaf(values))
+        }
+    }
+}
+
+/// Returns a copy of `node` without the last value, at the Rust 0-based index `idx`.
+///
+/// Returns `None` if the node is left empty.
+fn pop_in(node: &Node, shift: u32, idx: usize) -> Option<Rc<Node>> {
+    match node {
+        Node::Branch(children) => {
+            let mut children = children.clone();
+            let sub = (idx >> shift) & MASK;
+            match pop_in(&children[sub], shift - BITS, idx) {
+                Some(child) => children[sub] = child,
+                None => {
+                    children.pop();
+                }
+            }
+            (!children.is_empty()).then(|| Rc::new(Node::Branch(children)))
+        }
+        Node::Leaf(values) => {
+            let mut values = values.clone();
+            values.pop();
+            (!values.is_empty()).then(|| Rc::new(Node::Leaf(values)))
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use mlua::prelude::*;
+
+use crate::adapter::{LuaVectorAdapter, values_of};
+use crate::persistent::LuaPersistentVector;
+
+/// A type alias for the `PersistentVector` struct, used for clarity in Lua bindings.
+pub(super) type LuaPersistentVectorAdapter = PersistentVector;
+
+/// The adapter struct that wraps a `LuaPersistentVector` and implements the Lua-facing logic.
+///
+/// It exposes the read surface of `Vector`, while every update returns a new version
+/// instead of modifying the receiver.
+pub(super) struct PersistentVector {
+    /// The internal `LuaPersistentVector` that holds this version.
+    vec: LuaPersistentVector,
+}
+
+impl LuaPersistentVectorAdapter {
+    /// The Lua-facing constructor, registered as `vector.persistent(values)`.
+    ///
+    /// Accepts an optional `Vector` or table sequence holding the initial elements.
+    pub(super) fn new(_: &Lua, (values,): (Option<LuaValue>,)) -> LuaResult<Self> {
+        let values = match values {
+            Some(values) => values_of(&values)?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            vec: LuaPersistentVector::of(values),
+        })
+    }
+
+    /// The Lua-facing `Vector:to_persistent` method.
+    ///
+    /// Creates a persistent vector holding a copy of the elements of a `Vector`.
+    pub(super) fn from_vector(_: &Lua, this: &LuaVectorAdapter, _: ()) -> LuaResult<Self> {
+        Ok(Self {
+            vec: LuaPersistentVector::of(this.vec.as_slice().to_vec()),
+        })
+    }
+
+    /// The Lua-facing `get` method.
+    ///
+    /// It returns a presence flag and the value, exactly like `Vector:get`.
+    pub(super) fn get(
+        _: &Lua,
+        this: &Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(match this.vec.get(index) {
+            Some(value) => (LuaValue::Boolean(true), value),
+            None => (LuaValue::Boolean(false), LuaNil),
+        })
+    }
+
+    /// The Lua-facing `push` method.
+    ///
+    /// Returns a new version with `value` appended, leaving the receiver unchanged.
+    pub(super) fn push(_: &Lua, this: &Self, (value,): (LuaValue,)) -> LuaResult<Self> {
+        match this.vec.push(value) {
+            Some(vec) => Ok(Self { vec }),
+            None => Err(LuaError::runtime("Vector has reached its maximum length.")),
+        }
+    }
+
+    /// The Lua-facing `set` method.
+    ///
+    /// Returns a new version with the value at `index` replaced, leaving the receiver
+    /// unchanged. Causes a Lua error if the index is out of bounds, since there is no
+    /// version to return.
+    pub(super) fn set(
+        _: &Lua,
+        this: &Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<Self> {
+        match this.vec.set(index, value) {
+            Some(vec) => Ok(Self { vec }),
+            None => {
+                let msg = format!("Index out of bounds {}", index);
+                Err(LuaError::runtime(msg))
+            }
+        }
+    }
+
+    /// The Lua-facing `pop` method.
+    ///
+    /// Returns a presence flag, the last element and a new version without it, like
+    /// `Vector:pop` followed by the new version. On an empty vector, it returns `false`,
+    /// `nil` and the receiver itself.
+    pub(super) fn pop(
+        lua: &Lua,
+        this: LuaAnyUserData,
+    ) -> LuaResult<(LuaValue, LuaValue, LuaValue)> {
+        let popped = this.borrow::<Self>()?.vec.pop();
+
+        match popped {
+            Some((vec, value)) => {
+                let rest = lua.create_userdata(Self { vec })?;
+                Ok((LuaValue::Boolean(true), value, LuaValue::UserData(rest)))
+            }
+            None => Ok((LuaValue::Boolean(false), LuaNil, LuaValue::UserData(this))),
+        }
+    }
+
+    /// The Lua-facing `to_vector` method.
+    ///
+    /// Returns a new, mutable `Vector` with a copy of the elements.
+    pub(super) fn to_vector(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaVectorAdapter> {
+        Ok(LuaVectorAdapter {
+            vec: this.vec.to_vector(),
+        })
+    }
+
+    /// The Lua-facing `__index` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds.
+    pub(super) fn index(_: &Lua, this: &Self, (index,): (LuaInteger,)) -> LuaResult<LuaValue> {
+        match this.vec.get(index) {
+            Some(value) => Ok(value),
+
+            None => {
+                let msg = format!("Index out of bounds {}", index);
+                let lua_err = LuaError::runtime(msg);
+                Err(lua_err)
+            }
+        }
+    }
+
+    /// The Lua-facing `__len` metamethod.
+    pub(super) fn len(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.vec.len())
+    }
+
+    /// The Lua-facing `__eq` metamethod.
+    ///
+    /// Two persistent vectors are equal if they hold the same values in the same order.
+    pub(super) fn equals(_: &Lua, this: &Self, (other,): (LuaValue,)) -> LuaResult<LuaValue> {
+        let equals = match other {
+            LuaValue::UserData(ud) => ud.borrow::<Self>().is_ok_and(|other| this.vec == other.vec),
+            _ => false,
+        };
+
+        Ok(LuaValue::Boolean(equals))
+    }
+
+    /// The Lua-facing `__newindex` metamethod.
+    ///
+    /// Always causes a Lua error: persistent vectors are updated through `set`.
+    pub(super) fn newindex(_: &Lua, _: &Self, _: LuaMultiValue) -> LuaResult<()> {
+        Err(LuaError::runtime(
+            "Cannot assign to a persistent Vector, use `set` to create a new version.",
+        ))
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::VecDeque;
+
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, zero_based_index_in};
+
+/// The core implementation of a ring buffer, a queue with a fixed capacity.
+///
+/// Once the buffer is full, pushing a new value evicts the oldest one. Indices follow
+/// the `LuaVector` rules and are relative to the oldest element, so `1` is always the
+/// oldest value still held and `-1` the newest one.
+pub(crate) struct LuaRing {
+    /// The values held by the buffer, from the oldest to the newest.
+    inner: VecDeque<LuaValue>,
+    /// The maximum number of values held by the buffer.
+    capacity: usize,
+    /// The number of structural modifications (pushes and clears) made so far.
+    ///
+    /// Every push shifts the indices once the buffer is full, so iterators compare this
+    /// counter on every step like `LuaVectorCursor` does for vectors.
+    modifications: usize,
+}
+
+impl LuaRing {
+    /// Creates a new empty `LuaRing` holding at most `capacity` values.
+    ///
+    /// Returns an `Err` if the capacity is not a positive number.
+    pub(crate) fn new(capacity: LuaInteger) -> Result<Self, String> {
+        if capacity < 1 {
+            return Err(format!("Invalid ring capacity {}.", capacity));
+        }
+        Ok(Self {
+            inner: VecDeque::new(),
+            capacity: capacity as usize,
+            modifications: 0,
+        })
+    }
+
+    /// Retrieves a value based on a Lua 1-based index relative to the oldest element.
+    pub(crate) fn get(&self, index: LuaInteger) -> Option<LuaValue> {
+        self.zero_based_index(index)
+            .map(|idx| self.inner[idx].clone())
+    }
+
+    /// Returns the number of values currently held by the buffer.
+    pub(crate) fn len(&self) -> LuaInteger {
+        self.inner.len() as LuaInteger
+    }
+
+    /// Returns the maximum number of values held by the buffer.
+    pub(crate) fn capacity(&self) -> LuaInteger {
+        self.capacity as LuaInteger
+    }
+
+    /// Returns `true` if the next push will evict the oldest value.
+    pub(crate) fn is_full(&self) -> bool {
+        self.inner.len() == self.capacity
+    }
+
+    /// Sets the value at a given Lua index relative to the oldest element.
+    ///
+    /// Returns `true` on success, `false` if the index is out of bounds.
+    pub(crate) fn set(&mut self, index: LuaInteger, new_value: LuaValue) -> bool {
+        match self.zero_based_index(index) {
+            Some(idx) => {
+                self.inner[idx] = new_value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Appends a new value as the newest element.
+    ///
+    /// Returns the evicted oldest value if the buffer was full, or `None` otherwise.
+    pub(crate) fn push(&mut self, value: LuaValue) -> Option<LuaValue> {
+        let evicted = if self.is_full() {
+            self.inner.pop_front()
+        } else {
+            None
+        };
+        self.inner.push_back(value);
+        self.modifications += 1;
+        evicted
+    }
+
+    /// Removes all the values, keeping the capacity.
+    pub(crate) fn clear(&mut self) {
+        if !self.inner.is_empty() {
+            self.inner.clear();
+            self.modifications += 1;
+        }
+    }
+
+    /// Returns the number of structural modifications made to the buffer so far.
+    pub(crate) fn modifications(&self) -> usize {
+        self.modifications
+    }
+
+    /// Creates a `LuaVector` holding the same values, from the oldest to the newest.
+    pub(crate) fn to_vector(&self) -> LuaVector {
+        LuaVector::of(self.inner.iter().cloned().collect())
+    }
+
+    /// Converts a Lua 1-based index to a Rust 0-based index.
+    fn zero_based_index(&self, one_based_index: LuaInteger) -> Option<usize> {
+        zero_based_index_in(self.inner.len(), one_based_index)
+    }
+}
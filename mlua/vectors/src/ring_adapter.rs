@@ -0,0 +1,155 @@
+use mlua::prelude::*;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::ring::LuaRing;
+
+/// A type alias for the `Ring` struct, used for clarity in Lua bindings.
+pub(super) type LuaRingAdapter = Ring;
+
+/// The adapter struct that wraps a `LuaRing` and implements the Lua-facing logic.
+///
+/// It exposes the element access surface of `Vector`, with indices relative to the
+/// oldest value, and a `push` that reports the value evicted once the buffer is full.
+pub(super) struct Ring {
+    /// The internal `LuaRing` that holds the actual data.
+    ring: LuaRing,
+}
+
+impl LuaRingAdapter {
+    /// The Lua-facing constructor, registered as `vector.ring(capacity)`.
+    ///
+    /// Causes a Lua error if the capacity is not a positive number.
+    pub(super) fn new(_: &Lua, (capacity,): (LuaInteger,)) -> LuaResult<Self> {
+        match LuaRing::new(capacity) {
+            Ok(ring) => Ok(Self { ring }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing `get` method.
+    ///
+    /// It returns a presence flag and the value, exactly like `Vector:get`.
+    pub(super) fn get(
+        _: &Lua,
+        this: &Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(match this.ring.get(index) {
+            Some(value) => (LuaValue::Boolean(true), value),
+            None => (LuaValue::Boolean(false), LuaNil),
+        })
+    }
+
+    /// The Lua-facing `set` method.
+    ///
+    /// It returns `false` if the index is out of bounds, exactly like `Vector:set`.
+    pub(super) fn set(
+        _: &Lua,
+        this: &mut Self,
+        (index, new_value): (LuaInteger, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        Ok(LuaValue::Boolean(this.ring.set(index, new_value)))
+    }
+
+    /// The Lua-facing `push` method.
+    ///
+    /// Appends a value as the newest element. If the buffer was full, it returns `true`
+    /// and the evicted oldest value, otherwise `false` and `nil`.
+    pub(super) fn push(
+        _: &Lua,
+        this: &mut Self,
+        (value,): (LuaValue,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(match this.ring.push(value) {
+            Some(evicted) => (LuaValue::Boolean(true), evicted),
+            None => (LuaValue::Boolean(false), LuaNil),
+        })
+    }
+
+    /// The Lua-facing `is_full` method.
+    pub(super) fn is_full(_: &Lua, this: &Self, _: ()) -> LuaResult<bool> {
+        Ok(this.ring.is_full())
+    }
+
+    /// The Lua-facing `capacity` method, returning the maximum number of values held.
+    pub(super) fn capacity(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.ring.capacity())
+    }
+
+    /// The Lua-facing `clear` method.
+    pub(super) fn clear(_: &Lua, this: &mut Self, _: ()) -> LuaResult<()> {
+        this.ring.clear();
+        Ok(())
+    }
+
+    /// The Lua-facing `to_vector` method.
+    ///
+    /// Returns a `Vector` holding the same values, from the oldest to the newest.
+    pub(super) fn to_vector(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaVectorAdapter> {
+        Ok(LuaVectorAdapter {
+            vec: this.ring.to_vector(),
+        })
+    }
+
+    /// The Lua-facing `iter` method.
+    ///
+    /// Returns an iterator function yielding the index and value of every element, from
+    /// the oldest to the newest, like `Vector:iter`. The iterator causes a Lua error if
+    /// the buffer is pushed to or cleared during the traversal.
+    pub(super) fn iter(lua: &Lua, this: LuaAnyUserData) -> LuaResult<LuaFunction> {
+        let modifications = this.borrow::<Self>()?.ring.modifications();
+        let mut next = 1;
+
+        lua.create_function_mut(move |_, ()| {
+            let ring = &this.borrow::<Self>()?.ring;
+            if ring.modifications() != modifications {
+                return Err(LuaError::runtime("Ring was modified during iteration."));
+            }
+
+            match ring.get(next) {
+                Some(value) => {
+                    next += 1;
+                    Ok((LuaValue::Integer(next - 1), value))
+                }
+                None => Ok((LuaNil, LuaNil)),
+            }
+        })
+    }
+
+    /// The Lua-facing `__index` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds.
+    pub(super) fn index(_: &Lua, this: &Self, (index,): (LuaInteger,)) -> LuaResult<LuaValue> {
+        match this.ring.get(index) {
+            Some(value) => Ok(value),
+
+            None => {
+                let msg = format!("Index out of bounds {}", index);
+                let lua_err = LuaError::runtime(msg);
+                Err(lua_err)
+            }
+        }
+    }
+
+    /// The Lua-facing `__len` metamethod, returning the number of values currently held.
+    pub(super) fn len(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.ring.len())
+    }
+
+    /// The Lua-facing `__newindex` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds.
+    pub(super) fn newindex(
+        _: &Lua,
+        this: &mut Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<()> {
+        if !this.ring.set(index, value) {
+            let msg = format!("Index out of bounds {}", index);
+            let lua_err = LuaError::runtime(msg);
+            Err(lua_err)
+        } else {
+            Ok(())
+        }
+    }
+}
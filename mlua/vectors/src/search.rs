@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, as_float};
+
+/// A hashable key grouping the Lua values that may be equal to each other.
+///
+/// Values with different keys are never equal, while values sharing a key still have to
+/// be compared with `==`. Integers and floats share their `f64` key since Lua considers
+/// `1 == 1.0`, and reference types are keyed by pointer like their raw equality.
+#[derive(PartialEq, Eq, Hash)]
+pub(crate) enum EqBucket {
+    Nil,
+    Boolean(bool),
+    Number(u64),
+    String(Vec<u8>),
+    Reference(usize),
+}
+
+impl EqBucket {
+    /// Computes the bucket of a Lua value.
+    pub(crate) fn of(value: &LuaValue) -> Self {
+        match value {
+            LuaValue::Nil => Self::Nil,
+            LuaValue::Boolean(boolean) => Self::Boolean(*boolean),
+            LuaValue::Integer(_) | LuaValue::Number(_) => {
+                // `0.0` and `-0.0` are equal but have different bit patterns.
+                let num = as_float(value).unwrap_or_default();
+                Self::Number(if num == 0.0 { 0 } else { num.to_bits() })
+            }
+            LuaValue::String(string) => Self::String(string.as_bytes().to_vec()),
+            other => Self::Reference(other.to_pointer() as usize),
+        }
+    }
+}
+
+/// Searching and counting over the elements of a vector.
+///
+/// Elements are matched with the same equality as the `__eq` metamethod, which is the
+/// raw Lua equality: nested tables and vectors only match themselves.
+impl LuaVector {
+    /// Checks whether the vector contains an element equal to `value`.
+    pub(crate) fn contains(&self, value: &LuaValue) -> bool {
+        self.as_slice().contains(value)
+    }
+
+    /// Returns the Lua index of the first element equal to `value`, searching from `start`.
+    ///
+    /// `start` follows the 1-based, negative-from-the-end convention and is clamped to
+    /// the vector bounds like in `slice`.
+    pub(crate) fn index_of(&self, value: &LuaValue, start: LuaInteger) -> Option<LuaInteger> {
+        let (start, end) = self.clamped_range(start, -1);
+
+        self.as_slice()[start..end]
+            .iter()
+            .position(|element| element == value)
+            .map(|idx| (start + idx) as LuaInteger + 1)
+    }
+
+    /// Returns the Lua index of the last element equal to `value`.
+    pub(crate) fn last_index_of(&self, value: &LuaValue) -> Option<LuaInteger> {
+        self.as_slice()
+            .iter()
+            .rposition(|element| element == value)
+            .map(|idx| idx as LuaInteger + 1)
+    }
+
+    /// Counts the elements accepted by `pred`, which receives the Lua index and the value.
+    pub(crate) fn count_by<E>(
+        &self,
+        mut pred: impl FnMut(LuaInteger, &LuaValue) -> Result<bool, E>,
+    ) -> Result<LuaInteger, E> {
+        let mut count = 0;
+
+        for (idx, value) in self.as_slice().iter().enumerate() {
+            if pred(idx as LuaInteger + 1, value)? {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Creates a new `LuaVector` without duplicates, keeping the first occurrence of each
+    /// element in its original order.
+    pub(crate) fn unique(&self) -> Self {
+        let mut seen: HashMap<EqBucket, Vec<&LuaValue>> = HashMap::new();
+
+        let values = self
+            .as_slice()
+            .iter()
+            .filter(|&value| {
+                let bucket = seen.entry(EqBucket::of(value)).or_default();
+                if bucket.contains(&value) {
+                    return false;
+                }
+                bucket.push(value);
+                true
+            })
+            .cloned()
+            .collect();
+
+        Self::of(values)
+    }
+}
@@ -0,0 +1,242 @@
+use std::ffi::c_void;
+
+use mlua::prelude::*;
+use rmpv::Value as Packed;
+use serde_json::Value as Json;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::vector::LuaVector;
+
+/// The largest integer magnitude an `f64` holds exactly, used to render integral floats.
+const MAX_EXACT_FLOAT: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+
+/// Converts a Lua value into a MessagePack value tree, the intermediate representation
+/// shared by both the MessagePack and the JSON encoders.
+///
+/// `Vector`s and sequence tables become arrays (so explicit `nil`s inside vectors are
+/// kept), other tables become maps, and strings that are not valid UTF-8 become binary
+/// data. `visiting` holds the containers being encoded, to reject cyclic structures.
+/// Functions, threads and any other userdata cause a Lua error.
+pub(crate) fn encode(value: &LuaValue, visiting: &mut Vec<*const c_void>) -> LuaResult<Packed> {
+    match value {
+        LuaValue::Nil => Ok(Packed::Nil),
+        LuaValue::Boolean(boolean) => Ok(Packed::Boolean(*boolean)),
+        LuaValue::Integer(int) => Ok(Packed::from(*int)),
+        LuaValue::Number(num) => Ok(Packed::F64(*num)),
+        LuaValue::String(string) => Ok(match string.to_str() {
+            Ok(text) => Packed::from(&*text),
+            Err(_) => Packed::Binary(string.as_bytes().to_vec()),
+        }),
+        LuaValue::Table(table) => nested(table.to_pointer(), visiting, |visiting| {
+            encode_table(table, visiting)
+        }),
+        LuaValue::UserData(ud) if ud.is::<LuaVectorAdapter>() => {
+            nested(ud.to_pointer(), visiting, |visiting| {
+                let vec = ud.borrow::<LuaVectorAdapter>()?;
+                encode_values(vec.vec.as_slice(), visiting)
+            })
+        }
+        other => {
+            let msg = format!("Cannot encode a {} value.", other.type_name());
+            Err(LuaError::runtime(msg))
+        }
+    }
+}
+
+/// Converts a MessagePack value tree back into a `Vector`.
+///
+/// The top-level value must be an array. Nested arrays become `Vector`s, maps become
+/// tables, and `nil` elements are kept as present `nil`s.
+pub(crate) fn decode_vector(lua: &Lua, value: Packed) -> LuaResult<LuaVectorAdapter> {
+    match value {
+        Packed::Array(items) => {
+            let values = items
+                .into_iter()
+                .map(|item| decode(lua, item))
+                .collect::<LuaResult<_>>()?;
+
+            Ok(LuaVectorAdapter {
+                vec: LuaVector::of(values),
+            })
+        }
+        _ => Err(LuaError::runtime("Expected an array to decode a Vector.")),
+    }
+}
+
+/// Converts a MessagePack value tree into the equivalent JSON value.
+///
+/// Integral floats are written as JSON integers. Binary data, extension types,
+/// non-finite numbers and map keys other than strings and numbers cause a Lua error.
+pub(crate) fn to_json(value: Packed) -> LuaResult<Json> {
+    match value {
+        Packed::Nil => Ok(Json::Null),
+        Packed::Boolean(boolean) => Ok(Json::Bool(boolean)),
+        Packed::Integer(int) => Ok(match int.as_i64() {
+            Some(signed) => Json::from(signed),
+            None => Json::from(int.as_u64().unwrap_or_default()),
+        }),
+        Packed::F32(num) => float_to_json(num as f64),
+        Packed::F64(num) => float_to_json(num),
+        Packed::String(string) => match string.into_str() {
+            Some(text) => Ok(Json::String(text)),
+            None => Err(LuaError::runtime(
+                "Cannot encode a non UTF-8 string as JSON.",
+            )),
+        },
+        Packed::Array(items) => items
+            .into_iter()
+            .map(to_json)
+            .collect::<LuaResult<_>>()
+            .map(Json::Array),
+        Packed::Map(entries) => entries
+            .into_iter()
+            .map(|(key, value)| Ok((json_key(key)?, to_json(value)?)))
+            .collect::<LuaResult<_>>()
+            .map(Json::Object),
+        Packed::Binary(_) => Err(LuaError::runtime(
+            "Cannot encode a non UTF-8 string as JSON.",
+        )),
+        Packed::Ext(..) => Err(LuaError::runtime(
+            "Cannot encode an extension type as JSON.",
+        )),
+    }
+}
+
+/// Converts a JSON value into the equivalent MessagePack value tree.
+pub(crate) fn from_json(value: Json) -> Packed {
+    match value {
+        Json::Null => Packed::Nil,
+        Json::Bool(boolean) => Packed::Boolean(boolean),
+        Json::Number(num) => match (num.as_i64(), num.as_u64()) {
+            (Some(signed), _) => Packed::from(signed),
+            (None, Some(unsigned)) => Packed::from(unsigned),
+            _ => Packed::F64(num.as_f64().unwrap_or(f64::NAN)),
+        },
+        Json::String(text) => Packed::from(text),
+        Json::Array(items) => Packed::Array(items.into_iter().map(from_json).collect()),
+        Json::Object(entries) => Packed::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (Packed::from(key), from_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Runs `encode_inner` for a container, failing if it is already being encoded.
+fn nested(
+    ptr: *const c_void,
+    visiting: &mut Vec<*const c_void>,
+    encode_inner: impl FnOnce(&mut Vec<*const c_void>) -> LuaResult<Packed>,
+) -> LuaResult<Packed> {
+    if visiting.contains(&ptr) {
+        return Err(LuaError::runtime("Cannot encode a cyclic structure."));
+    }
+
+    visiting.push(ptr);
+    let result = encode_inner(visiting);
+    visiting.pop();
+
+    result
+}
+
+/// Encodes a slice of Lua values as an array.
+fn encode_values(values: &[LuaValue], visiting: &mut Vec<*const c_void>) -> LuaResult<Packed> {
+    values
+        .iter()
+        .map(|value| encode(value, visiting))
+        .collect::<LuaResult<_>>()
+        .map(Packed::Array)
+}
+
+/// Encodes a table as an array if its keys are exactly `1..=n`, or as a map otherwise.
+///
+/// Empty tables are encoded as empty maps.
+fn encode_table(table: &LuaTable, visiting: &mut Vec<*const c_void>) -> LuaResult<Packed> {
+    let entries = table
+        .pairs::<LuaValue, LuaValue>()
+        .collect::<LuaResult<Vec<_>>>()?;
+
+    let is_sequence = !entries.is_empty()
+        && entries.iter().all(|(key, _)| {
+            sequence_index(key).is_some_and(|idx| idx >= 1 && idx <= entries.len() as LuaInteger)
+        });
+
+    if is_sequence {
+        let mut values = vec![LuaNil; entries.len()];
+        for (key, value) in entries {
+            let idx = sequence_index(&key).unwrap_or_default() as usize;
+            values[idx - 1] = value;
+        }
+        return encode_values(&values, visiting);
+    }
+
+    entries
+        .iter()
+        .map(|(key, value)| Ok((encode(key, visiting)?, encode(value, visiting)?)))
+        .collect::<LuaResult<_>>()
+        .map(Packed::Map)
+}
+
+/// Converts a MessagePack value into a Lua value, creating `Vector`s for arrays.
+fn decode(lua: &Lua, value: Packed) -> LuaResult<LuaValue> {
+    match value {
+        Packed::Nil => Ok(LuaNil),
+        Packed::Boolean(boolean) => Ok(LuaValue::Boolean(boolean)),
+        Packed::Integer(int) => Ok(match int.as_i64() {
+            Some(signed) => LuaValue::Integer(signed),
+            None => LuaValue::Number(int.as_f64().unwrap_or_default()),
+        }),
+        Packed::F32(num) => Ok(LuaValue::Number(num as f64)),
+        Packed::F64(num) => Ok(LuaValue::Number(num)),
+        Packed::String(string) => lua.create_string(string.as_bytes()).map(LuaValue::String),
+        Packed::Binary(bytes) => lua.create_string(bytes).map(LuaValue::String),
+        Packed::Array(_) => {
+            let vec = decode_vector(lua, value)?;
+            lua.create_userdata(vec).map(LuaValue::UserData)
+        }
+        Packed::Map(entries) => {
+            let table = lua.create_table_with_capacity(0, entries.len())?;
+            for (key, value) in entries {
+                table.raw_set(decode(lua, key)?, decode(lua, value)?)?;
+            }
+            Ok(LuaValue::Table(table))
+        }
+        Packed::Ext(..) => Err(LuaError::runtime(
+            "Cannot decode a MessagePack extension type.",
+        )),
+    }
+}
+
+/// Reads a table key as a sequence index, accepting integral floats from Lua 5.1.
+fn sequence_index(key: &LuaValue) -> Option<LuaInteger> {
+    match key {
+        LuaValue::Integer(int) => Some(*int),
+        LuaValue::Number(num) if num.fract() == 0.0 && num.abs() < MAX_EXACT_FLOAT => {
+            Some(*num as LuaInteger)
+        }
+        _ => None,
+    }
+}
+
+/// Converts a float into a JSON number, writing integral values as integers.
+fn float_to_json(num: f64) -> LuaResult<Json> {
+    if num.fract() == 0.0 && num.abs() < MAX_EXACT_FLOAT {
+        return Ok(Json::from(num as i64));
+    }
+
+    serde_json::Number::from_f64(num)
+        .map(Json::Number)
+        .ok_or_else(|| LuaError::runtime("Cannot encode NaN or infinity as JSON."))
+}
+
+/// Converts a map key into a JSON object key. Only strings and numbers are accepted.
+fn json_key(key: Packed) -> LuaResult<String> {
+    match to_json(key)? {
+        Json::String(text) => Ok(text),
+        Json::Number(num) => Ok(num.to_string()),
+        _ => Err(LuaError::runtime(
+            "JSON object keys must be strings or numbers.",
+        )),
+    }
+}
@@ -0,0 +1,245 @@
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, zero_based_index_in};
+
+/// The magnitude of the `i64` bounds as an `f64`: `i64::MIN` is `-I64_BOUND`, and
+/// `i64::MAX` rounds up to `I64_BOUND` when converted.
+const I64_BOUND: f64 = 9_223_372_036_854_775_808.0;
+
+/// A numeric element type that can be stored packed in a `LuaTypedVector`.
+///
+/// Implementors define how Lua values are converted to the packed representation.
+/// Conversions must be lossless: a Lua value that cannot be represented exactly is
+/// rejected instead of being truncated. The only exception is `f32`, whose purpose is to
+/// round numbers to single precision.
+pub(crate) trait Numeric: Copy + PartialEq + 'static {
+    /// The name of the element type, as used by the Lua constructors and in error messages.
+    const NAME: &'static str;
+
+    /// Converts a Lua value to the packed representation.
+    ///
+    /// Returns `None` if the value is not a number or cannot be represented exactly.
+    fn from_lua_value(value: &LuaValue) -> Option<Self>;
+
+    /// Converts a Lua value for binary packing, rounding it the way `string.pack` does.
+    ///
+    /// Only the floating-point types round; the others use `from_lua_value`.
+    fn round_lua_value(value: &LuaValue) -> Option<Self> {
+        Self::from_lua_value(value)
+    }
+
+    /// Converts the packed representation back to a Lua value.
+    fn to_lua_value(self) -> LuaValue;
+}
+
+impl Numeric for f64 {
+    const NAME: &'static str = "f64";
+
+    /// Integers are accepted only if an `f64` represents them exactly.
+    fn from_lua_value(value: &LuaValue) -> Option<Self> {
+        match value {
+            LuaValue::Integer(int) => {
+                let num = *int as f64;
+                (num < I64_BOUND && num as i64 == *int).then_some(num)
+            }
+            LuaValue::Number(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    fn round_lua_value(value: &LuaValue) -> Option<Self> {
+        match value {
+            LuaValue::Integer(int) => Some(*int as f64),
+            LuaValue::Number(num) => Some(*num),
+            _ => None,
+        }
+    }
+
+    fn to_lua_value(self) -> LuaValue {
+        LuaValue::Number(self)
+    }
+}
+
+impl Numeric for f32 {
+    const NAME: &'static str = "f32";
+
+    /// Numbers are rounded to the nearest `f32`, which is the purpose of this type. Finite
+    /// numbers beyond its range are rejected rather than turned into infinities.
+    fn from_lua_value(value: &LuaValue) -> Option<Self> {
+        let num = match value {
+            LuaValue::Integer(int) => *int as f64,
+            LuaValue::Number(num) => *num,
+            _ => return None,
+        };
+        let single = num as f32;
+        (single.is_finite() || !num.is_finite()).then_some(single)
+    }
+
+    fn round_lua_value(value: &LuaValue) -> Option<Self> {
+        f64::round_lua_value(value).map(|num| num as f32)
+    }
+
+    fn to_lua_value(self) -> LuaValue {
+        LuaValue::Number(self as f64)
+    }
+}
+
+impl Numeric for i64 {
+    const NAME: &'static str = "i64";
+
+    /// Floats are accepted only if they hold an integral value within the `i64` range.
+    fn from_lua_value(value: &LuaValue) -> Option<Self> {
+        match value {
+            LuaValue::Integer(int) => Some(*int),
+            LuaValue::Number(num)
+                if num.fract() == 0.0 && (-I64_BOUND..I64_BOUND).contains(num) =>
+            {
+                Some(*num as i64)
+            }
+            _ => None,
+        }
+    }
+
+    fn to_lua_value(self) -> LuaValue {
+        LuaValue::Integer(self)
+    }
+}
+
+impl Numeric for i32 {
+    const NAME: &'static str = "i32";
+
+    fn from_lua_value(value: &LuaValue) -> Option<Self> {
+        i64::from_lua_value(value).and_then(|int| i32::try_from(int).ok())
+    }
+
+    fn to_lua_value(self) -> LuaValue {
+        LuaValue::Integer(self as LuaInteger)
+    }
+}
+
+/// The core implementation of a vector with packed numeric storage.
+///
+/// It mirrors the indexing rules of `LuaVector` (1-based, negative indices counting
+/// from the end, presence through `Option`) but stores plain `T` values instead of
+/// `LuaValue`s, which is both smaller and faster for numeric work.
+#[derive(Clone, PartialEq)]
+pub(crate) struct LuaTypedVector<T: Numeric> {
+    /// The internal `Vec` that stores the packed values.
+    inner: Vec<T>,
+}
+
+impl<T: Numeric> LuaTypedVector<T> {
+    /// The maximum length of the vector, constrained by the Lua `integer` type.
+    const MAX_LEN: usize = LuaInteger::MAX as usize;
+
+    /// Creates a new `LuaTypedVector` instance with a given size and filler value.
+    ///
+    /// Returns an `Err` if the size is a negative number or cannot be allocated.
+    pub(crate) fn new(size: LuaInteger, filler: T) -> Result<Self, String> {
+        if size < 0 {
+            return Err(format!("Invalid Vector len {}.", size));
+        }
+        let mut inner = Vec::new();
+        inner
+            .try_reserve(size as usize)
+            .map_err(|_| format!("Cannot allocate memory for {} more elements.", size))?;
+        inner.resize(size as usize, filler);
+        Ok(Self { inner })
+    }
+
+    /// Creates a new `LuaTypedVector` by converting every given Lua value.
+    ///
+    /// Returns an `Err` describing the first value that cannot be stored losslessly.
+    pub(crate) fn try_of(values: &[LuaValue]) -> Result<Self, String> {
+        let inner = values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                T::from_lua_value(value)
+                    .ok_or_else(|| conversion_error::<T>(idx as LuaInteger + 1, value))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { inner })
+    }
+
+    /// Retrieves a value based on a Lua 1-based index, following the `LuaVector` rules.
+    pub(crate) fn get(&self, index: LuaInteger) -> Option<T> {
+        self.zero_based_index(index).map(|idx| self.inner[idx])
+    }
+
+    /// Returns the number of elements in the vector.
+    pub(crate) fn len(&self) -> LuaInteger {
+        self.inner.len() as LuaInteger
+    }
+
+    /// Appends a new value to the end of the vector.
+    ///
+    /// Returns `false` if the vector has reached its maximum capacity.
+    pub(crate) fn push(&mut self, value: T) -> bool {
+        if self.inner.len() < Self::MAX_LEN {
+            self.inner.push(value);
+            return true;
+        }
+        false
+    }
+
+    /// Removes and returns the last element of the vector.
+    ///
+    /// Returns `None` if the vector is empty.
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    /// Sets the value at a given Lua index.
+    ///
+    /// Returns `true` on success, `false` if the index is out of bounds.
+    pub(crate) fn set(&mut self, index: LuaInteger, new_value: T) -> bool {
+        match self.zero_based_index(index) {
+            Some(idx) => {
+                self.inner[idx] = new_value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Compares two `LuaTypedVector` instances for pointer equality.
+    pub(crate) fn is_same(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+
+    /// Creates a generic `LuaVector` holding the same values.
+    pub(crate) fn to_vector(&self) -> LuaVector {
+        LuaVector::of(
+            self.inner
+                .iter()
+                .map(|value| value.to_lua_value())
+                .collect(),
+        )
+    }
+
+    /// Converts a Lua 1-based index to a Rust 0-based index.
+    fn zero_based_index(&self, one_based_index: LuaInteger) -> Option<usize> {
+        zero_based_index_in(self.inner.len(), one_based_index)
+    }
+}
+
+/// Builds the error message for a Lua value that cannot be stored as `T`.
+///
+/// `index` is the Lua 1-based position of the value, used to locate it in bulk conversions.
+pub(crate) fn conversion_error<T: Numeric>(index: LuaInteger, value: &LuaValue) -> String {
+    match value {
+        LuaValue::Integer(_) | LuaValue::Number(_) => format!(
+            "Value at index {} has no exact {} representation.",
+            index,
+            T::NAME
+        ),
+        _ => format!(
+            "Value at index {} is a {}, expected a number for a {} Vector.",
+            index,
+            value.type_name(),
+            T::NAME
+        ),
+    }
+}
@@ -0,0 +1,179 @@
+use mlua::prelude::*;
+
+use crate::adapter::{LuaVectorAdapter, values_of};
+use crate::typed::{LuaTypedVector, Numeric, conversion_error};
+
+/// A type alias for the `TypedVector` struct, used for clarity in Lua bindings.
+pub(super) type LuaTypedVectorAdapter<T> = TypedVector<T>;
+
+/// The adapter struct that wraps a `LuaTypedVector` and implements the Lua-facing logic.
+///
+/// It exposes the same surface as `Vector`, with the difference that writes of values
+/// that cannot be stored losslessly as `T` raise a Lua error.
+pub(super) struct TypedVector<T: Numeric> {
+    /// The internal `LuaTypedVector` that holds the packed data.
+    vec: LuaTypedVector<T>,
+}
+
+impl<T: Numeric> LuaTypedVectorAdapter<T> {
+    /// The Lua-facing constructor, registered as `vector.<type>(size, filler)`.
+    ///
+    /// The size defaults to `0` and the filler to `0`. Causes a Lua error if the filler
+    /// is not a number representable as `T`.
+    pub(super) fn new(
+        _: &Lua,
+        (size, filler): (Option<LuaInteger>, Option<LuaValue>),
+    ) -> LuaResult<Self> {
+        let vec_size = size.unwrap_or(0);
+        let vec_filler = to_element(1, &filler.unwrap_or(LuaValue::Integer(0)))?;
+
+        match LuaTypedVector::new(vec_size, vec_filler) {
+            Ok(vec) => Ok(Self { vec }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing conversion constructor, registered as `vector.<type>_from(values)`.
+    ///
+    /// Accepts a `Vector` or a table sequence, and causes a Lua error if any element
+    /// cannot be stored losslessly as `T`.
+    pub(super) fn from(_: &Lua, (values,): (LuaValue,)) -> LuaResult<Self> {
+        let values = values_of(&values)?;
+
+        match LuaTypedVector::try_of(&values) {
+            Ok(vec) => Ok(Self { vec }),
+            Err(msg) => Err(LuaError::RuntimeError(msg)),
+        }
+    }
+
+    /// The Lua-facing `get` method.
+    ///
+    /// It returns a presence flag and the value, exactly like `Vector:get`.
+    pub(super) fn get(
+        _: &Lua,
+        this: &Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        Ok(match this.vec.get(index) {
+            Some(value) => (LuaValue::Boolean(true), value.to_lua_value()),
+            None => (LuaValue::Boolean(false), LuaNil),
+        })
+    }
+
+    /// The Lua-facing `is_same` method, which checks for reference equality.
+    pub(super) fn is_same(_: &Lua, this: &Self, (other,): (LuaValue,)) -> LuaResult<LuaValue> {
+        let is_same = match other {
+            LuaValue::UserData(ud) => ud
+                .borrow::<Self>()
+                .is_ok_and(|other| this.vec.is_same(&other.vec)),
+            _ => false,
+        };
+
+        Ok(LuaValue::Boolean(is_same))
+    }
+
+    /// The Lua-facing `set` method.
+    ///
+    /// It returns `false` if the index is out of bounds, and causes a Lua error if the
+    /// value cannot be stored as `T`.
+    pub(super) fn set(
+        _: &Lua,
+        this: &mut Self,
+        (index, new_value): (LuaInteger, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        let value = to_element(index, &new_value)?;
+
+        Ok(LuaValue::Boolean(this.vec.set(index, value)))
+    }
+
+    /// The Lua-facing `push` method.
+    ///
+    /// Appends a value and returns `true` on success. Causes a Lua error if the value
+    /// cannot be stored as `T`.
+    pub(super) fn push(_: &Lua, this: &mut Self, (value,): (LuaValue,)) -> LuaResult<LuaValue> {
+        let value = to_element(this.vec.len() + 1, &value)?;
+
+        Ok(LuaValue::Boolean(this.vec.push(value)))
+    }
+
+    /// The Lua-facing `pop` method.
+    ///
+    /// It removes and returns the last element, along with a presence flag.
+    pub(super) fn pop(_: &Lua, this: &mut Self, _: ()) -> LuaResult<(LuaValue, LuaValue)> {
+        match this.vec.pop() {
+            Some(value) => Ok((LuaValue::Boolean(true), value.to_lua_value())),
+            None => Ok((LuaValue::Boolean(false), LuaNil)),
+        }
+    }
+
+    /// The Lua-facing `to_vector` method.
+    ///
+    /// Returns a generic `Vector` holding the same values.
+    pub(super) fn to_vector(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaVectorAdapter> {
+        Ok(LuaVectorAdapter {
+            vec: this.vec.to_vector(),
+        })
+    }
+
+    /// The Lua-facing `element_type` method, returning the name of `T` (e.g. `"f64"`).
+    pub(super) fn element_type(_: &Lua, _: &Self, _: ()) -> LuaResult<&'static str> {
+        Ok(T::NAME)
+    }
+
+    /// The Lua-facing `__index` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds.
+    pub(super) fn index(_: &Lua, this: &Self, (index,): (LuaInteger,)) -> LuaResult<LuaValue> {
+        match this.vec.get(index) {
+            Some(value) => Ok(value.to_lua_value()),
+
+            None => {
+                let msg = format!("Index out of bounds {}", index);
+                let lua_err = LuaError::runtime(msg);
+                Err(lua_err)
+            }
+        }
+    }
+
+    /// The Lua-facing `__len` metamethod.
+    pub(super) fn len(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        Ok(this.vec.len())
+    }
+
+    /// The Lua-facing `__eq` metamethod.
+    ///
+    /// Two typed vectors are equal if they have the same element type and values.
+    pub(super) fn equals(_: &Lua, this: &Self, (other,): (LuaValue,)) -> LuaResult<LuaValue> {
+        let equals = match other {
+            LuaValue::UserData(ud) => ud.borrow::<Self>().is_ok_and(|other| this.vec == other.vec),
+            _ => false,
+        };
+
+        Ok(LuaValue::Boolean(equals))
+    }
+
+    /// The Lua-facing `__newindex` metamethod.
+    ///
+    /// Causes a Lua runtime error if the index is out of bounds or the value cannot be
+    /// stored as `T`.
+    pub(super) fn newindex(
+        _: &Lua,
+        this: &mut Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<()> {
+        let value = to_element(index, &value)?;
+
+        if !this.vec.set(index, value) {
+            let msg = format!("Index out of bounds {}", index);
+            let lua_err = LuaError::runtime(msg);
+            Err(lua_err)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Converts a Lua value written at `index` to `T`, raising a Lua error if it is not possible.
+fn to_element<T: Numeric>(index: LuaInteger, value: &LuaValue) -> LuaResult<T> {
+    T::from_lua_value(value).ok_or_else(|| LuaError::runtime(conversion_error::<T>(index, value)))
+}
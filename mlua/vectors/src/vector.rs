@@ -1,20 +1,62 @@
 use mlua::prelude::*;
 
+use crate::history::{Edit, LuaHistory};
+use crate::observe::Change;
+
 /// The core implementation of the vector, containing the business logic.
 ///
 /// This struct holds the actual data (`Vec<LuaValue>`) and performs all vector
 /// operations. It is designed to be independent of the `mlua` binding layer,
-/// allowing for reusable logic. The `PartialEq` derive implements a deep
-/// comparison for the `__eq` metamethod.
-#[derive(PartialEq)]
+/// allowing for reusable logic. The `PartialEq` implementation performs a deep
+/// comparison of the elements for the `__eq` metamethod.
 pub(crate) struct LuaVector {
     /// The internal `Vec` that stores the Lua values.
     inner: Vec<LuaValue>,
+    /// The number of structural modifications (changes in length) made so far.
+    ///
+    /// Iterators remember this counter when created and compare it on every step
+    /// to detect that the vector was modified while being traversed.
+    modifications: usize,
+    /// Whether the vector has been frozen, making it immutable from Lua.
+    ///
+    /// The core operations do not check this flag: the adapter layer does, so that
+    /// mutators called on a frozen vector raise a Lua error instead of failing silently.
+    frozen: bool,
+    /// The changes made since they were last taken, or `None` when they are not recorded.
+    ///
+    /// Recording is only enabled while the vector has observers, and the adapter layer
+    /// takes the changes after every mutator to dispatch them.
+    changes: Option<Vec<Change>>,
+    /// The undo and redo history, or `None` when it is not tracked.
+    history: Option<LuaHistory>,
+}
+
+/// Clones the elements and the frozen state, but neither the recorded changes nor the
+/// history: observers and undo steps belong to the original vector.
+impl Clone for LuaVector {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            modifications: self.modifications,
+            frozen: self.frozen,
+            changes: None,
+            history: None,
+        }
+    }
+}
+
+impl PartialEq for LuaVector {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
 }
 
 impl LuaVector {
     /// The maximum length of the vector, constrained by the Lua `integer` type.
-    const MAX_LEN: usize = LuaInteger::MAX as usize;
+    pub(crate) const MAX_LEN: usize = LuaInteger::MAX as usize;
+
+    /// The maximum number of elements rendered by `display_with` before truncating.
+    const DISPLAY_LIMIT: usize = 32;
 
     /// Creates a new `LuaVector` instance with a given size and filler value.
     ///
@@ -23,14 +65,29 @@ impl LuaVector {
         if size < 0 {
             return Err(format!("Invalid Vector len {}.", size));
         }
-        Ok(Self {
-            inner: vec![filler; size as usize],
-        })
+        let mut vec = Self::with_capacity(size)?;
+        vec.inner.resize(size as usize, filler);
+        Ok(vec)
+    }
+
+    /// Creates a new empty `LuaVector` able to hold `capacity` elements without reallocating.
+    ///
+    /// Returns an `Err` if the capacity is negative or cannot be allocated.
+    pub(crate) fn with_capacity(capacity: LuaInteger) -> Result<Self, String> {
+        let mut vec = Self::of(Vec::new());
+        vec.reserve(capacity)?;
+        Ok(vec)
     }
 
     /// Creates a new `LuaVector` from a pre-existing Rust `Vec<LuaValue>`.
     pub(crate) fn of(values: Vec<LuaValue>) -> Self {
-        Self { inner: values }
+        Self {
+            inner: values,
+            modifications: 0,
+            frozen: false,
+            changes: None,
+            history: None,
+        }
     }
 
     /// Retrieves a value from the vector based on a Lua 1-based index.
@@ -39,7 +96,8 @@ impl LuaVector {
     /// retrieves the element at that position, while a negative index `-j` retrieves
     /// the element `j` from the end of the vector.
     pub(crate) fn get(&self, index: LuaInteger) -> Option<LuaValue> {
-        self.zero_based_index(index).map(|idx| self.inner[idx].clone())
+        self.zero_based_index(index)
+            .map(|idx| self.inner[idx].clone())
     }
 
     /// Returns the number of elements in the vector.
@@ -53,7 +111,11 @@ impl LuaVector {
     /// has reached its maximum capacity.
     pub(crate) fn push(&mut self, value: LuaValue) -> bool {
         if self.inner.len() < Self::MAX_LEN {
+            if self.is_recording() {
+                self.record_element(self.inner.len(), None, Some(value.clone()));
+            }
             self.inner.push(value);
+            self.modifications += 1;
             return true;
         }
         false
@@ -63,7 +125,12 @@ impl LuaVector {
     ///
     /// Returns `None` if the vector is empty.
     pub(crate) fn pop(&mut self) -> Option<LuaValue> {
-        self.inner.pop()
+        let value = self.inner.pop()?;
+        self.modifications += 1;
+        if self.is_recording() {
+            self.record_element(self.inner.len(), Some(value.clone()), None);
+        }
+        Some(value)
     }
 
     /// Sets the value at a given Lua index.
@@ -73,13 +140,599 @@ impl LuaVector {
     pub(crate) fn set(&mut self, index: LuaInteger, new_value: LuaValue) -> bool {
         match self.zero_based_index(index) {
             Some(idx) => {
-                self.inner[idx] = new_value;
+                let old_value = std::mem::replace(&mut self.inner[idx], new_value);
+                if self.is_recording() {
+                    let new_value = self.inner[idx].clone();
+                    self.record_element(idx, Some(old_value), Some(new_value));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts a value so that it ends up at the given Lua index.
+    ///
+    /// Valid indices range from `1` to `len + 1`; negative indices count from the end of
+    /// the resulting vector, so `-1` appends. Returns `false` if the index is out of
+    /// bounds or the vector has reached its maximum capacity.
+    pub(crate) fn insert(&mut self, index: LuaInteger, value: LuaValue) -> bool {
+        if self.inner.len() >= Self::MAX_LEN {
+            return false;
+        }
+        match zero_based_index_in(self.inner.len() + 1, index) {
+            Some(idx) => {
+                if self.is_recording() {
+                    self.record_element(idx, None, Some(value.clone()));
+                }
+                self.inner.insert(idx, value);
+                self.modifications += 1;
                 true
             }
             None => false,
         }
     }
 
+    /// Removes and returns the element at the given Lua index, shifting the following ones.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub(crate) fn remove(&mut self, index: LuaInteger) -> Option<LuaValue> {
+        let idx = self.zero_based_index(index)?;
+        let value = self.inner.remove(idx);
+        self.modifications += 1;
+        if self.is_recording() {
+            self.record_element(idx, Some(value.clone()), None);
+        }
+        Some(value)
+    }
+
+    /// Removes and returns the element at the given Lua index, replacing it with the last one.
+    ///
+    /// This does not preserve ordering, but runs in constant time.
+    /// Returns `None` if the index is out of bounds.
+    pub(crate) fn swap_remove(&mut self, index: LuaInteger) -> Option<LuaValue> {
+        let idx = self.zero_based_index(index)?;
+        let value = self.inner.swap_remove(idx);
+        self.modifications += 1;
+        if self.is_recording() {
+            let last = self.inner.len();
+            match self.inner.get(idx).cloned() {
+                Some(moved) => {
+                    self.record_element(idx, Some(value.clone()), Some(moved.clone()));
+                    self.record_element(last, Some(moved), None);
+                }
+                None => self.record_element(last, Some(value.clone()), None),
+            }
+        }
+        Some(value)
+    }
+
+    /// Removes `count` elements starting at the given Lua index and inserts `values` in their place.
+    ///
+    /// The index follows the usual rules, and `len + 1` is also accepted to splice at the
+    /// end. `count` is clamped to the number of available elements. Returns the removed
+    /// elements, or `None` if the index is out of bounds, `count` is negative, or the
+    /// result would exceed the maximum capacity.
+    pub(crate) fn splice(
+        &mut self,
+        index: LuaInteger,
+        count: LuaInteger,
+        values: Vec<LuaValue>,
+    ) -> Option<Self> {
+        let len = self.inner.len();
+        let start = match index {
+            idx if idx == len as LuaInteger + 1 => len,
+            idx => self.zero_based_index(idx)?,
+        };
+        if count < 0 {
+            return None;
+        }
+
+        let end = start + (count as usize).min(len - start);
+        if len - (end - start) + values.len() > Self::MAX_LEN {
+            return None;
+        }
+        self.record_bulk();
+        let removed = self.inner.splice(start..end, values).collect();
+        self.modifications += 1;
+
+        Some(Self::of(removed))
+    }
+
+    /// Appends all the given values to the end of the vector.
+    ///
+    /// Returns `false`, leaving the vector unchanged, if the result would exceed the
+    /// maximum capacity.
+    pub(crate) fn extend(&mut self, values: Vec<LuaValue>) -> bool {
+        if self.inner.len() + values.len() > Self::MAX_LEN {
+            return false;
+        }
+        if !values.is_empty() {
+            self.record_bulk();
+        }
+        self.inner.extend(values);
+        self.modifications += 1;
+        true
+    }
+
+    /// Shortens the vector, keeping the first `len` elements.
+    ///
+    /// Has no effect if `len` is greater than the current length.
+    /// Returns an `Err` if `len` is a negative number.
+    pub(crate) fn truncate(&mut self, len: LuaInteger) -> Result<(), String> {
+        if len < 0 {
+            return Err(format!("Invalid Vector len {}.", len));
+        }
+        if (len as usize) < self.inner.len() {
+            self.record_tail(len as usize);
+            self.inner.truncate(len as usize);
+            self.modifications += 1;
+        }
+        Ok(())
+    }
+
+    /// Removes all the elements of the vector.
+    pub(crate) fn clear(&mut self) {
+        if !self.inner.is_empty() {
+            self.record_tail(0);
+            self.inner.clear();
+            self.modifications += 1;
+        }
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub(crate) fn capacity(&self) -> LuaInteger {
+        self.inner.capacity().min(Self::MAX_LEN) as LuaInteger
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// Returns an `Err`, leaving the vector unchanged, if `additional` is negative, if the
+    /// resulting capacity would exceed the maximum length, or if the allocation fails.
+    pub(crate) fn reserve(&mut self, additional: LuaInteger) -> Result<(), String> {
+        if additional < 0 {
+            return Err(format!("Invalid Vector capacity {}.", additional));
+        }
+        if additional as usize > Self::MAX_LEN - self.inner.len() {
+            return Err(format!(
+                "Cannot reserve {} more elements: the capacity would exceed the maximum Vector len.",
+                additional
+            ));
+        }
+        self.inner
+            .try_reserve(additional as usize)
+            .map_err(|_| format!("Cannot allocate memory for {} more elements.", additional))
+    }
+
+    /// Shrinks the capacity of the vector as much as possible, down to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
+    }
+
+    /// Swaps the elements at two Lua indices.
+    ///
+    /// Returns `false`, leaving the vector unchanged, if either index is out of bounds.
+    pub(crate) fn swap(&mut self, i: LuaInteger, j: LuaInteger) -> bool {
+        match (self.zero_based_index(i), self.zero_based_index(j)) {
+            (Some(a), Some(b)) => {
+                self.inner.swap(a, b);
+                if a != b && self.is_recording() {
+                    let (old, new) = (self.inner[b].clone(), self.inner[a].clone());
+                    self.record_element(a, Some(old.clone()), Some(new.clone()));
+                    self.record_element(b, Some(new), Some(old));
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reverses the order of the elements in place.
+    pub(crate) fn reverse(&mut self) {
+        if self.inner.len() > 1 {
+            self.record_bulk();
+        }
+        self.inner.reverse();
+    }
+
+    /// Removes consecutive repeated elements in place, keeping the first of each run.
+    ///
+    /// Elements are compared with the same equality as `PartialEq`.
+    pub(crate) fn dedup(&mut self) {
+        if self.inner.windows(2).any(|pair| pair[0] == pair[1]) {
+            self.record_bulk();
+            self.inner.dedup();
+            self.modifications += 1;
+        }
+    }
+
+    /// Rotates the elements in place by `n` positions.
+    ///
+    /// A positive `n` moves elements towards the end, wrapping the last ones to the
+    /// front; a negative `n` rotates in the opposite direction.
+    pub(crate) fn rotate(&mut self, n: LuaInteger) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let shift = n.rem_euclid(len) as usize;
+        if shift != 0 {
+            self.record_bulk();
+            self.inner.rotate_right(shift);
+        }
+    }
+
+    /// Marks the vector as frozen. Freezing cannot be undone.
+    pub(crate) fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    /// Returns `true` if the vector has been frozen.
+    pub(crate) fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Creates a new, mutable `LuaVector` holding a copy of the elements.
+    pub(crate) fn thawed_copy(&self) -> Self {
+        Self::of(self.inner.clone())
+    }
+
+    /// Enables or disables the recording of changes, discarding the changes recorded so far.
+    pub(crate) fn record_changes(&mut self, enabled: bool) {
+        self.changes = enabled.then(Vec::new);
+    }
+
+    /// Ends a mutation made from Lua.
+    ///
+    /// Closes the undo step of the mutation if the history is tracked, and takes the
+    /// changes recorded for observers since the last call, leaving recording enabled.
+    pub(crate) fn finish_mutation(&mut self) -> Vec<Change> {
+        if let Some(history) = &mut self.history {
+            history.commit();
+        }
+        self.changes
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Returns the undo and redo history slot, which is `None` when it is not tracked.
+    pub(crate) fn history_mut(&mut self) -> &mut Option<LuaHistory> {
+        &mut self.history
+    }
+
+    /// Replaces all the elements at once, returning the previous ones.
+    pub(crate) fn replace(&mut self, values: Vec<LuaValue>) -> Vec<LuaValue> {
+        self.record_bulk();
+        self.modifications += 1;
+        std::mem::replace(&mut self.inner, values)
+    }
+
+    /// Returns `true` if changes are recorded, either for observers or for the history.
+    fn is_recording(&self) -> bool {
+        self.changes.is_some() || self.history.is_some()
+    }
+
+    /// Records a change to the element at a Rust 0-based index.
+    ///
+    /// `old` is `None` for an added element, and `new` is `None` for a removed one.
+    fn record_element(&mut self, idx: usize, old: Option<LuaValue>, new: Option<LuaValue>) {
+        let index = Self::one_based_index(idx);
+        if let Some(history) = &mut self.history {
+            let (old, new) = (old.clone(), new.clone());
+            history.record(Edit::Element { index, old, new });
+        }
+        if let Some(changes) = &mut self.changes {
+            changes.push(Change::Element { index, old, new });
+        }
+    }
+
+    /// Records a change involving several elements, if recording is enabled.
+    ///
+    /// It must be called before the elements change, so that the history can keep the
+    /// previous ones.
+    fn record_bulk(&mut self) {
+        if let Some(history) = self.history.as_mut().filter(|h| !h.is_snapshotted()) {
+            history.record(Edit::Contents(self.inner.clone()));
+        }
+        if let Some(changes) = &mut self.changes {
+            changes.push(Change::Bulk);
+        }
+    }
+
+    /// Records the removal of the elements from a Rust 0-based index to the end, if
+    /// recording is enabled.
+    ///
+    /// It must be called before the elements are removed. Observers are notified as for
+    /// `record_bulk`, but the history only keeps the removed elements.
+    fn record_tail(&mut self, len: usize) {
+        if let Some(history) = self.history.as_mut().filter(|h| !h.is_snapshotted()) {
+            history.record(Edit::Tail(self.inner[len..].to_vec()));
+        }
+        if let Some(changes) = &mut self.changes {
+            changes.push(Change::Bulk);
+        }
+    }
+
+    /// Returns the number of structural modifications made to the vector so far.
+    ///
+    /// Every operation that changes the length of the vector increments this counter.
+    pub(crate) fn modifications(&self) -> usize {
+        self.modifications
+    }
+
+    /// Returns the elements of the vector as a Rust slice.
+    pub(crate) fn as_slice(&self) -> &[LuaValue] {
+        &self.inner
+    }
+
+    /// Creates a new `LuaVector` by applying `f` to every element.
+    ///
+    /// The callback receives the Lua 1-based index and the element. The first
+    /// error returned by the callback stops the traversal and is propagated.
+    pub(crate) fn map<E>(
+        &self,
+        mut f: impl FnMut(LuaInteger, &LuaValue) -> Result<LuaValue, E>,
+    ) -> Result<Self, E> {
+        let mut inner = Vec::with_capacity(self.inner.len());
+
+        for (idx, value) in self.inner.iter().enumerate() {
+            inner.push(f(Self::one_based_index(idx), value)?);
+        }
+        Ok(Self::of(inner))
+    }
+
+    /// Creates a new `LuaVector` holding only the elements accepted by `pred`.
+    ///
+    /// The relative order of the kept elements is preserved.
+    pub(crate) fn filter<E>(
+        &self,
+        mut pred: impl FnMut(LuaInteger, &LuaValue) -> Result<bool, E>,
+    ) -> Result<Self, E> {
+        let mut inner = Vec::new();
+
+        for (idx, value) in self.inner.iter().enumerate() {
+            if pred(Self::one_based_index(idx), value)? {
+                inner.push(value.clone());
+            }
+        }
+        Ok(Self::of(inner))
+    }
+
+    /// Folds every element into an accumulator, starting from `init`.
+    ///
+    /// When `init` is `None`, the first element is used as the initial accumulator
+    /// and the traversal starts at the second one. Returns `None` only when there
+    /// is no initial value and the vector is empty.
+    pub(crate) fn fold<E>(
+        &self,
+        init: Option<LuaValue>,
+        mut f: impl FnMut(LuaValue, LuaInteger, &LuaValue) -> Result<LuaValue, E>,
+    ) -> Result<Option<LuaValue>, E> {
+        let (mut acc, skip) = match init {
+            Some(value) => (value, 0),
+            None => match self.inner.first() {
+                Some(first) => (first.clone(), 1),
+                None => return Ok(None),
+            },
+        };
+
+        for (idx, value) in self.inner.iter().enumerate().skip(skip) {
+            acc = f(acc, Self::one_based_index(idx), value)?;
+        }
+        Ok(Some(acc))
+    }
+
+    /// Returns the Lua 1-based index of the first element accepted by `pred`.
+    ///
+    /// Returns `None` if no element satisfies the predicate.
+    pub(crate) fn position<E>(
+        &self,
+        mut pred: impl FnMut(LuaInteger, &LuaValue) -> Result<bool, E>,
+    ) -> Result<Option<LuaInteger>, E> {
+        for (idx, value) in self.inner.iter().enumerate() {
+            let index = Self::one_based_index(idx);
+            if pred(index, value)? {
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks whether at least one element is accepted by `pred`.
+    ///
+    /// Stops at the first accepted element. An empty vector yields `false`.
+    pub(crate) fn any<E>(
+        &self,
+        pred: impl FnMut(LuaInteger, &LuaValue) -> Result<bool, E>,
+    ) -> Result<bool, E> {
+        Ok(self.position(pred)?.is_some())
+    }
+
+    /// Checks whether every element is accepted by `pred`.
+    ///
+    /// Stops at the first rejected element. An empty vector yields `true`.
+    pub(crate) fn all<E>(
+        &self,
+        mut pred: impl FnMut(LuaInteger, &LuaValue) -> Result<bool, E>,
+    ) -> Result<bool, E> {
+        let rejected = self.position(|index, value| Ok(!pred(index, value)?))?;
+        Ok(rejected.is_none())
+    }
+
+    /// Calls `f` on every element, in order, stopping at the first error.
+    pub(crate) fn for_each<E>(
+        &self,
+        mut f: impl FnMut(LuaInteger, &LuaValue) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for (idx, value) in self.inner.iter().enumerate() {
+            f(Self::one_based_index(idx), value)?;
+        }
+        Ok(())
+    }
+
+    /// Sorts the vector in place using the `less` comparator.
+    ///
+    /// The sort is not stable: equal elements may be reordered. The elements are
+    /// sorted on a copy, so the vector is left untouched if the comparator fails.
+    /// Returns `Ok(false)` without modifying the vector if the comparator turned out
+    /// to be inconsistent (e.g. it reported `b < a` for an already ordered pair).
+    pub(crate) fn sort_by<E>(
+        &mut self,
+        mut less: impl FnMut(&LuaValue, &LuaValue) -> Result<bool, E>,
+    ) -> Result<bool, E> {
+        let mut sorted = self.inner.clone();
+        heap_sort(&mut sorted, &mut less)?;
+
+        if !is_sorted_by(&sorted, &mut less)? {
+            return Ok(false);
+        }
+        self.record_bulk();
+        self.inner = sorted;
+        Ok(true)
+    }
+
+    /// Sorts the vector in place using the `less` comparator, preserving the order
+    /// of equal elements.
+    ///
+    /// Follows the same failure rules as [`LuaVector::sort_by`].
+    pub(crate) fn sort_stable_by<E>(
+        &mut self,
+        mut less: impl FnMut(&LuaValue, &LuaValue) -> Result<bool, E>,
+    ) -> Result<bool, E> {
+        let sorted = merge_sort(self.inner.clone(), &mut less)?;
+
+        if !is_sorted_by(&sorted, &mut less)? {
+            return Ok(false);
+        }
+        self.record_bulk();
+        self.inner = sorted;
+        Ok(true)
+    }
+
+    /// Searches a sorted vector for `value` using the `less` comparator.
+    ///
+    /// Returns `Ok(index)` with the Lua 1-based index of a matching element, or
+    /// `Err(index)` with the 1-based index where `value` could be inserted while
+    /// keeping the vector sorted.
+    pub(crate) fn binary_search_by<E>(
+        &self,
+        value: &LuaValue,
+        mut less: impl FnMut(&LuaValue, &LuaValue) -> Result<bool, E>,
+    ) -> Result<Result<LuaInteger, LuaInteger>, E> {
+        let (mut low, mut high) = (0, self.inner.len());
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let element = &self.inner[mid];
+
+            if less(element, value)? {
+                low = mid + 1;
+            } else if less(value, element)? {
+                high = mid;
+            } else {
+                return Ok(Ok(Self::one_based_index(mid)));
+            }
+        }
+        Ok(Err(Self::one_based_index(low)))
+    }
+
+    /// Returns the Lua 1-based index of the first element rejected by `pred`.
+    ///
+    /// The vector must be partitioned so that every accepted element comes before
+    /// every rejected one. Returns `len + 1` if all elements are accepted.
+    pub(crate) fn partition_point<E>(
+        &self,
+        mut pred: impl FnMut(&LuaValue) -> Result<bool, E>,
+    ) -> Result<LuaInteger, E> {
+        let (mut low, mut high) = (0, self.inner.len());
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if pred(&self.inner[mid])? {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(Self::one_based_index(low))
+    }
+
+    /// The default ordering used when no comparator is provided.
+    ///
+    /// Numbers are compared numerically and strings byte by byte. Returns an `Err`
+    /// for any other combination, including `NaN` and mixed numbers and strings.
+    pub(crate) fn default_less(a: &LuaValue, b: &LuaValue) -> Result<bool, String> {
+        match (a, b) {
+            (LuaValue::Integer(x), LuaValue::Integer(y)) => Ok(x < y),
+            (LuaValue::String(x), LuaValue::String(y)) => Ok(x.as_bytes() < y.as_bytes()),
+            _ => match (as_float(a), as_float(b)) {
+                (Some(x), Some(y)) if !x.is_nan() && !y.is_nan() => Ok(x < y),
+                _ => Err(format!(
+                    "Cannot compare {} with {}.",
+                    a.type_name(),
+                    b.type_name()
+                )),
+            },
+        }
+    }
+
+    /// Creates a new `LuaVector` with a copy of the elements between `i` and `j`, inclusive.
+    ///
+    /// Both indices follow the 1-based, negative-from-the-end convention and are
+    /// clamped to the vector bounds, like Lua's `string.sub`. An empty vector is
+    /// returned when the range is empty.
+    pub(crate) fn slice(&self, i: LuaInteger, j: LuaInteger) -> Self {
+        let (start, end) = self.clamped_range(i, j);
+
+        Self::of(self.inner[start..end].to_vec())
+    }
+
+    /// Converts an inclusive Lua range `i..=j` into a Rust `start..end` range.
+    ///
+    /// The range is clamped to the vector bounds, and `start <= end` always holds.
+    pub(crate) fn clamped_range(&self, i: LuaInteger, j: LuaInteger) -> (usize, usize) {
+        let len = self.len();
+
+        let first = match i {
+            idx if idx < 0 => (len + idx + 1).max(1),
+            0 => 1,
+            idx => idx,
+        };
+        let last = match j {
+            idx if idx < 0 => (len + idx + 1).max(0),
+            idx => idx.min(len),
+        };
+
+        let start = (first - 1).min(len) as usize;
+        let end = (last as usize).max(start);
+        (start, end)
+    }
+
+    /// Renders the vector as `Vector(a, b, c)`, using `render` to convert each element.
+    ///
+    /// At most `DISPLAY_LIMIT` elements are rendered; the remaining ones are summarized
+    /// as `... (n more)` so that printing a huge vector stays cheap.
+    pub(crate) fn display_with<E>(
+        &self,
+        mut render: impl FnMut(&LuaValue) -> Result<String, E>,
+    ) -> Result<String, E> {
+        let mut parts = Vec::with_capacity(self.inner.len().min(Self::DISPLAY_LIMIT) + 1);
+
+        for value in self.inner.iter().take(Self::DISPLAY_LIMIT) {
+            parts.push(render(value)?);
+        }
+        if self.inner.len() > Self::DISPLAY_LIMIT {
+            parts.push(format!(
+                "... ({} more)",
+                self.inner.len() - Self::DISPLAY_LIMIT
+            ));
+        }
+
+        Ok(format!("Vector({})", parts.join(", ")))
+    }
+
     /// Compares two `LuaVector` instances for pointer equality.
     ///
     /// This method is a helper for the `is_same` Lua method, which performs a reference
@@ -95,14 +748,120 @@ impl LuaVector {
     /// - Negative `idx` -> `idx + len`
     /// - Returns `None` if the index is out of bounds.
     fn zero_based_index(&self, one_based_index: LuaInteger) -> Option<usize> {
-        let len = self.inner.len() as LuaInteger;
+        zero_based_index_in(self.inner.len(), one_based_index)
+    }
+
+    /// A helper function to convert a Rust 0-based index to a Lua 1-based index.
+    fn one_based_index(zero_based_index: usize) -> LuaInteger {
+        zero_based_index as LuaInteger + 1
+    }
+}
 
-        match one_based_index {
-            0 => None,
-            idx if idx > len => None,
-            idx if idx < -len => None,
-            idx if idx > 0 => Some((idx - 1) as usize),
-            idx => Some((idx + len) as usize),
+/// Converts a Lua 1-based index to a Rust 0-based index for a sequence of length `len`.
+///
+/// Follows the same rules as [`LuaVector::get`]: negative indices count from the end,
+/// and `None` is returned if the index is out of bounds.
+pub(crate) fn zero_based_index_in(len: usize, one_based_index: LuaInteger) -> Option<usize> {
+    let len = len as LuaInteger;
+
+    match one_based_index {
+        0 => None,
+        idx if idx > len => None,
+        idx if idx < -len => None,
+        idx if idx > 0 => Some((idx - 1) as usize),
+        idx => Some((idx + len) as usize),
+    }
+}
+
+/// Sorts `values` in place with a heap sort driven by a fallible `less` comparator.
+///
+/// Unlike the standard library sorts, this never panics on an inconsistent comparator.
+fn heap_sort<E>(
+    values: &mut [LuaValue],
+    less: &mut impl FnMut(&LuaValue, &LuaValue) -> Result<bool, E>,
+) -> Result<(), E> {
+    let len = values.len();
+
+    for start in (0..len / 2).rev() {
+        sift_down(values, start, len, less)?;
+    }
+    for end in (1..len).rev() {
+        values.swap(0, end);
+        sift_down(values, 0, end, less)?;
+    }
+    Ok(())
+}
+
+/// Restores the max-heap property for the subtree rooted at `root`, within `values[..end]`.
+fn sift_down<E>(
+    values: &mut [LuaValue],
+    mut root: usize,
+    end: usize,
+    less: &mut impl FnMut(&LuaValue, &LuaValue) -> Result<bool, E>,
+) -> Result<(), E> {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            return Ok(());
+        }
+        if child + 1 < end && less(&values[child], &values[child + 1])? {
+            child += 1;
+        }
+        if !less(&values[root], &values[child])? {
+            return Ok(());
+        }
+        values.swap(root, child);
+        root = child;
+    }
+}
+
+/// Sorts `values` with a stable merge sort driven by a fallible `less` comparator.
+fn merge_sort<E>(
+    mut values: Vec<LuaValue>,
+    less: &mut impl FnMut(&LuaValue, &LuaValue) -> Result<bool, E>,
+) -> Result<Vec<LuaValue>, E> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+
+    let right = values.split_off(values.len() / 2);
+    let mut left = merge_sort(values, less)?.into_iter().peekable();
+    let mut right = merge_sort(right, less)?.into_iter().peekable();
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // Taking from the right only when strictly smaller keeps the sort stable.
+        let next = if less(r, l)? {
+            right.next()
+        } else {
+            left.next()
+        };
+        merged.extend(next);
+    }
+    merged.extend(left);
+    merged.extend(right);
+
+    Ok(merged)
+}
+
+/// Checks that no adjacent pair of `values` is reported out of order by `less`.
+fn is_sorted_by<E>(
+    values: &[LuaValue],
+    less: &mut impl FnMut(&LuaValue, &LuaValue) -> Result<bool, E>,
+) -> Result<bool, E> {
+    for pair in values.windows(2) {
+        if less(&pair[1], &pair[0])? {
+            return Ok(false);
         }
     }
-}
\ No newline at end of file
+    Ok(true)
+}
+
+/// Reads a Lua integer or float as an `f64`, returning `None` for any other type.
+pub(crate) fn as_float(value: &LuaValue) -> Option<f64> {
+    match value {
+        LuaValue::Integer(int) => Some(*int as f64),
+        LuaValue::Number(num) => Some(*num),
+        _ => None,
+    }
+}
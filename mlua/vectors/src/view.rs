@@ -0,0 +1,74 @@
+use mlua::prelude::*;
+
+use crate::vector::{LuaVector, zero_based_index_in};
+
+/// The core implementation of a vector view, a window over a range of a parent vector.
+///
+/// The view does not own any data: it only remembers where its window starts and how
+/// long it was when created. Every operation receives the parent `LuaVector` and
+/// re-checks the window against its current length, so a view over a vector that
+/// has shrunk simply becomes shorter (or empty) instead of reading stale memory.
+pub(crate) struct LuaVectorView {
+    /// The Rust 0-based index, in the parent, of the first element of the window.
+    start: usize,
+    /// The length of the window at the time the view was created.
+    len: usize,
+}
+
+impl LuaVectorView {
+    /// Creates a new `LuaVectorView` over the elements between `i` and `j`, inclusive.
+    ///
+    /// The indices are clamped to the parent bounds in the same way as `LuaVector::slice`.
+    pub(crate) fn new(parent: &LuaVector, i: LuaInteger, j: LuaInteger) -> Self {
+        let (start, end) = parent.clamped_range(i, j);
+
+        Self {
+            start,
+            len: end - start,
+        }
+    }
+
+    /// Returns the number of elements currently visible through the view.
+    ///
+    /// This is the creation length, shortened if the parent no longer holds that many
+    /// elements after the start of the window.
+    pub(crate) fn len(&self, parent: &LuaVector) -> LuaInteger {
+        let available = (parent.len() as usize).saturating_sub(self.start);
+
+        self.len.min(available) as LuaInteger
+    }
+
+    /// Retrieves a value through the view based on a Lua 1-based index.
+    ///
+    /// Supports the same positive and negative indexing as `LuaVector::get`,
+    /// relative to the window.
+    pub(crate) fn get(&self, parent: &LuaVector, index: LuaInteger) -> Option<LuaValue> {
+        self.parent_index(parent, index)
+            .and_then(|idx| parent.get(idx))
+    }
+
+    /// Sets a value through the view based on a Lua 1-based index.
+    ///
+    /// Returns `true` on success, `false` if the index is out of the window bounds.
+    pub(crate) fn set(&self, parent: &mut LuaVector, index: LuaInteger, value: LuaValue) -> bool {
+        match self.parent_index(parent, index) {
+            Some(idx) => parent.set(idx, value),
+            None => false,
+        }
+    }
+
+    /// Creates a new `LuaVector` holding a copy of the elements visible through the view.
+    pub(crate) fn to_vector(&self, parent: &LuaVector) -> LuaVector {
+        let first = self.start as LuaInteger + 1;
+        let last = self.start as LuaInteger + self.len(parent);
+
+        parent.slice(first, last)
+    }
+
+    /// Converts a Lua 1-based index in the window to a positive 1-based index in the parent.
+    fn parent_index(&self, parent: &LuaVector, index: LuaInteger) -> Option<LuaInteger> {
+        let len = self.len(parent) as usize;
+
+        zero_based_index_in(len, index).map(|idx| (self.start + idx) as LuaInteger + 1)
+    }
+}
@@ -0,0 +1,137 @@
+use mlua::prelude::*;
+
+use crate::adapter::LuaVectorAdapter;
+use crate::observe_adapter;
+use crate::view::LuaVectorView;
+
+/// A type alias for the `VectorView` struct, used for clarity in Lua bindings.
+pub(super) type LuaVectorViewAdapter = VectorView;
+
+/// The adapter struct that exposes a `LuaVectorView` to Lua.
+///
+/// It keeps a reference to the parent `Vector` userdata, which keeps the parent alive
+/// for as long as the view exists, and borrows it on every access so that reads and
+/// writes go straight through to the parent storage.
+pub(super) struct VectorView {
+    /// The parent `Vector` userdata the view reads from and writes to.
+    parent: LuaAnyUserData,
+    /// The core view that tracks the window over the parent.
+    view: LuaVectorView,
+}
+
+impl LuaVectorViewAdapter {
+    /// The Lua-facing constructor for `Vector:view(i, j)`.
+    ///
+    /// It is registered as a function rather than a method because the view needs to
+    /// hold the parent userdata itself. `i` defaults to `1` and `j` defaults to `-1`.
+    pub(super) fn new(
+        _: &Lua,
+        (parent, i, j): (LuaAnyUserData, Option<LuaInteger>, Option<LuaInteger>),
+    ) -> LuaResult<Self> {
+        let view = {
+            let parent_ref = parent.borrow::<LuaVectorAdapter>()?;
+            LuaVectorView::new(&parent_ref.vec, i.unwrap_or(1), j.unwrap_or(-1))
+        };
+
+        Ok(Self { parent, view })
+    }
+
+    /// The Lua-facing `get` method.
+    ///
+    /// It returns a presence flag and the value, exactly like `Vector:get`.
+    pub(super) fn get(
+        _: &Lua,
+        this: &Self,
+        (index,): (LuaInteger,),
+    ) -> LuaResult<(LuaValue, LuaValue)> {
+        let parent = this.parent.borrow::<LuaVectorAdapter>()?;
+
+        Ok(match this.view.get(&parent.vec, index) {
+            Some(value) => (LuaValue::Boolean(true), value),
+            None => (LuaValue::Boolean(false), LuaNil),
+        })
+    }
+
+    /// The Lua-facing `set` method.
+    ///
+    /// It writes through to the parent and returns a boolean indicating whether the
+    /// index was within the view bounds. Causes a Lua error if the parent is frozen.
+    pub(super) fn set(
+        _: &Lua,
+        this: &Self,
+        (index, new_value): (LuaInteger, LuaValue),
+    ) -> LuaResult<LuaValue> {
+        let (succeed, changes) = {
+            let mut parent = this.parent.borrow_mut::<LuaVectorAdapter>()?;
+            parent.ensure_mutable("set")?;
+            let succeed = this.view.set(&mut parent.vec, index, new_value);
+            (succeed, parent.vec.finish_mutation())
+        };
+
+        observe_adapter::notify(&this.parent, "set", changes)?;
+        Ok(LuaValue::Boolean(succeed))
+    }
+
+    /// The Lua-facing `to_vector` method.
+    ///
+    /// Returns a new, independent `Vector` with a copy of the visible elements.
+    pub(super) fn to_vector(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaVectorAdapter> {
+        let parent = this.parent.borrow::<LuaVectorAdapter>()?;
+
+        Ok(LuaVectorAdapter {
+            vec: this.view.to_vector(&parent.vec),
+        })
+    }
+
+    /// The Lua-facing `__index` metamethod.
+    ///
+    /// Like `Vector`'s `__index`, it causes a Lua runtime error if the index is out of
+    /// the view bounds.
+    pub(super) fn index(_: &Lua, this: &Self, (index,): (LuaInteger,)) -> LuaResult<LuaValue> {
+        let parent = this.parent.borrow::<LuaVectorAdapter>()?;
+
+        match this.view.get(&parent.vec, index) {
+            Some(value) => Ok(value),
+
+            None => {
+                let msg = format!("Index out of bounds {}", index);
+                let lua_err = LuaError::runtime(msg);
+                Err(lua_err)
+            }
+        }
+    }
+
+    /// The Lua-facing `__len` metamethod.
+    ///
+    /// Returns the number of elements currently visible through the view.
+    pub(super) fn len(_: &Lua, this: &Self, _: ()) -> LuaResult<LuaInteger> {
+        let parent = this.parent.borrow::<LuaVectorAdapter>()?;
+
+        Ok(this.view.len(&parent.vec))
+    }
+
+    /// The Lua-facing `__newindex` metamethod.
+    ///
+    /// Like `Vector`'s `__newindex`, it causes a Lua runtime error if the index is out
+    /// of the view bounds or the parent is frozen.
+    pub(super) fn newindex(
+        _: &Lua,
+        this: &Self,
+        (index, value): (LuaInteger, LuaValue),
+    ) -> LuaResult<()> {
+        let (succeed, changes) = {
+            let mut parent = this.parent.borrow_mut::<LuaVectorAdapter>()?;
+            parent.ensure_mutable("__newindex")?;
+            let succeed = this.view.set(&mut parent.vec, index, value);
+            (succeed, parent.vec.finish_mutation())
+        };
+
+        if !succeed {
+            let msg = format!("Index out of bounds {}", index);
+            let lua_err = LuaError::runtime(msg);
+            Err(lua_err)
+        } else {
+            observe_adapter::notify(&this.parent, "__newindex", changes)
+        }
+    }
+}
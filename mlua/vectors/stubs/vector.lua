@@ -3,6 +3,24 @@
 local vector = {};
 
 ---@class Vector A Lua object that provides a dynamic array with common vector operations.
+---
+--- Vectors of numbers support the `+`, `-`, `*` and `/` operators, element-wise with another
+--- vector of the same length or broadcast with a number, and unary `-`. They raise an error
+--- for vectors of different lengths or non-numeric elements.
+---@operator add(Vector<number> | number): Vector<number>
+---@operator sub(Vector<number> | number): Vector<number>
+---@operator mul(Vector<number> | number): Vector<number>
+---@operator div(Vector<number> | number): Vector<number>
+---@operator unm: Vector<number>
+---
+--- The `..` operator concatenates two vectors (or a vector and a table sequence) into a new
+--- vector, and `tostring` renders vectors as `Vector(1, "two", nil)`.
+---@operator concat(Vector | any[]): Vector
+---
+--- The `<` and `<=` operators order vectors lexicographically, so vectors can be sorted.
+---
+--- On Lua 5.2+, `pairs` iterates over the elements, and on Lua 5.4 a vector declared as
+--- `local v <close>` releases its spare capacity when it goes out of scope.
 local Vector = {}
 
 --- Creates a new vector.
@@ -26,6 +44,55 @@ function vector.of(...)
 
 end
 
+--- Creates a new vector from the elements `1` to `n` of a table.
+---
+--- When `n` is given, holes (`nil` values) up to that length are kept in the vector.
+--- Without it, the raw length of the table is used. Causes an error if `n` is negative.
+---@generic T
+---@param t T[] The table to copy the elements from.
+---@param n? integer The number of elements to copy.
+---@return Vector<T>
+function vector.from_table(t, n)
+end
+
+--- Creates the inclusive arithmetic progression from `start` to `stop`.
+---
+--- Integer arguments produce integers. Float steps produce `start + i * step` for exactly
+--- the elements within the bounds, so `vector.range(0, 1, 0.1)` has 11 elements. A step
+--- going away from `stop` produces an empty vector. Causes an error if `step` is zero.
+---@param start number The first element.
+---@param stop number The inclusive bound.
+---@param step? number The difference between elements, `1` by default.
+---@return Vector<number>
+function vector.range(start, stop, step)
+end
+
+--- Creates a new vector holding `n` copies of `value`. Causes an error if `n` is negative.
+---@generic T
+---@param value T The repeated value.
+---@param n integer The number of copies.
+---@return Vector<T>
+function vector.rep(value, n)
+end
+
+--- Creates a new vector of `n` elements, where the element at index `i` is `func(i)`.
+---@generic T
+---@param n integer The number of elements.
+---@param func fun(i: integer): T
+---@return Vector<T>
+function vector.generate(n, func)
+end
+
+--- Creates a new vector from a generic `for` iterator, collecting the first value of every step.
+---
+--- Accepts the iterator triplet directly, as in `vector.from_iter(string.gmatch(s, "%a+"))`.
+---@param iter function The iterator function.
+---@param state? any The invariant state.
+---@param control? any The initial control value.
+---@return Vector
+function vector.from_iter(iter, state, control)
+end
+
 --- Retrieves an element from the vector by its index.
 ---
 --- This method returns two values: a presence flag and the element's value.
@@ -78,6 +145,1318 @@ end
 function Vector.is_same(self, other)
 end
 
+--- Creates a new vector by applying a function to every element.
+---
+--- The callback is called as `func(value, index)`. If it raises an error, the error is
+--- propagated with the index of the element being processed.
+---@generic T, U
+---@param self Vector<T>
+---@param func fun(value: T, index: integer): U The transformation applied to each element.
+---@return Vector<U> -- A new vector with the transformed elements.
+function Vector.map(self, func)
+end
+
+--- Creates a new vector with only the elements accepted by the predicate.
+---@generic T
+---@param self Vector<T>
+---@param pred fun(value: T, index: integer): any The predicate; truthy results keep the element.
+---@return Vector<T> -- A new vector with the kept elements, in their original order.
+function Vector.filter(self, pred)
+end
+
+--- Reduces the vector to a single value.
+---
+--- The callback is called as `func(acc, value, index)`. When `init` is omitted the first
+--- element is used as the initial accumulator, and an empty vector reduces to `nil`.
+---@generic T, U
+---@param self Vector<T>
+---@param func fun(acc: U, value: T, index: integer): U The accumulating function.
+---@param init? U The initial accumulator.
+---@return U | nil -- The final accumulator.
+function Vector.reduce(self, func, init)
+end
+
+--- Searches for the first element accepted by the predicate.
+---
+--- This method returns three values: a presence flag, the element's value and its index.
+--- It returns `false, nil, nil` when no element matches.
+---@generic T
+---@param self Vector<T>
+---@param pred fun(value: T, index: integer): any The predicate.
+---@return boolean, T | nil, integer | nil -- The presence flag, the element and its index.
+function Vector.find(self, pred)
+end
+
+--- Checks whether at least one element is accepted by the predicate.
+---@generic T
+---@param self Vector<T>
+---@param pred fun(value: T, index: integer): any The predicate.
+---@return boolean -- `true` if any element matches; `false` for an empty vector.
+function Vector.any(self, pred)
+end
+
+--- Checks whether every element is accepted by the predicate.
+---@generic T
+---@param self Vector<T>
+---@param pred fun(value: T, index: integer): any The predicate.
+---@return boolean -- `true` if all elements match; `true` for an empty vector.
+function Vector.all(self, pred)
+end
+
+--- Calls a function on every element, in order.
+---@generic T
+---@param self Vector<T>
+---@param func fun(value: T, index: integer) The function to call.
+function Vector.for_each(self, func)
+end
+
+--- Sorts the vector in place.
+---
+--- The comparator is called as `cmp(a, b)` and must return `true` when `a` should come
+--- before `b`. Without a comparator, numbers and strings are sorted in ascending order,
+--- nested vectors lexicographically like `<`, and any other element raises an error. The
+--- sort is not stable. An error is also raised
+--- when the comparator is inconsistent, in which case the vector is left unchanged.
+---@generic T
+---@param self Vector<T>
+---@param cmp? fun(a: T, b: T): boolean The "less than" comparator.
+function Vector.sort(self, cmp)
+end
+
+--- Sorts the vector in place, preserving the relative order of equal elements.
+---
+--- Accepts the same comparator and raises the same errors as `sort`.
+---@generic T
+---@param self Vector<T>
+---@param cmp? fun(a: T, b: T): boolean The "less than" comparator.
+function Vector.sort_stable(self, cmp)
+end
+
+--- Returns a stably sorted copy of the vector, leaving the original unchanged.
+---@generic T
+---@param self Vector<T>
+---@param cmp? fun(a: T, b: T): boolean The "less than" comparator.
+---@return Vector<T> -- The sorted copy.
+function Vector.sorted(self, cmp)
+end
+
+--- Searches a sorted vector for a value.
+---
+--- This method returns two values: a presence flag and an index. When the value is found,
+--- the index is its position; otherwise it is the position where the value could be
+--- inserted to keep the vector sorted.
+---@generic T
+---@param self Vector<T>
+---@param value T The value to search for.
+---@param cmp? fun(a: T, b: T): boolean The "less than" comparator the vector is sorted by.
+---@return boolean, integer -- The presence flag and the index.
+function Vector.binary_search(self, value, cmp)
+end
+
+--- Returns the index of the first element rejected by the predicate.
+---
+--- The vector must be partitioned: every accepted element comes before every rejected one.
+--- Returns `#self + 1` if every element is accepted.
+---@generic T
+---@param self Vector<T>
+---@param pred fun(value: T): any The predicate.
+---@return integer -- The partition point.
+function Vector.partition_point(self, pred)
+end
+
+--- Returns a copy of the elements between `i` and `j`, inclusive.
+---
+--- Both indices accept negative values counting from the end, and are clamped to the
+--- vector bounds like `string.sub`. An empty vector is returned for an empty range.
+---@generic T
+---@param self Vector<T>
+---@param i? integer The first index of the range. Defaults to `1`.
+---@param j? integer The last index of the range. Defaults to `-1`.
+---@return Vector<T> -- A new vector with the copied elements.
+function Vector.slice(self, i, j)
+end
+
+--- Returns a view over the elements between `i` and `j`, inclusive.
+---
+--- The view shares the storage of this vector: reads and writes through it go straight
+--- to this vector. The indices are interpreted like in `slice`. If this vector later
+--- shrinks, the view shrinks with it.
+---@generic T
+---@param self Vector<T>
+---@param i? integer The first index of the range. Defaults to `1`.
+---@param j? integer The last index of the range. Defaults to `-1`.
+---@return VectorView<T> -- A view over the range.
+function Vector.view(self, i, j)
+end
+
+---@class VectorView A window over a range of a parent `Vector`, sharing its storage.
+local VectorView = {}
+
+--- Retrieves an element through the view, with the same presence flag as `Vector.get`.
+---@generic T
+---@param self VectorView<T>
+---@param index integer The index of the element, relative to the view.
+---@return boolean, T | nil -- A boolean flag indicating presence and the element's value.
+function VectorView.get(self, index)
+end
+
+--- Sets an element through the view, writing to the parent vector.
+---@generic T
+---@param self VectorView<T>
+---@param index integer The index of the element, relative to the view.
+---@param value T The new value.
+---@return boolean -- Returns `true` if the value was set, `false` otherwise.
+function VectorView.set(self, index, value)
+end
+
+--- Copies the elements currently visible through the view into a new vector.
+---@generic T
+---@param self VectorView<T>
+---@return Vector<T> -- A new, independent vector.
+function VectorView.to_vector(self)
+end
+
+--- Inserts a value so that it ends up at the given index, shifting the following elements.
+---
+--- Valid indices range from `1` to `#self + 1`. Negative indices count from the end of the
+--- resulting vector, so `-1` appends the value.
+---@generic T
+---@param self Vector<T>
+---@param index integer The index the value will occupy.
+---@param value T The value to insert.
+---@return boolean -- Returns `true` if the value was inserted, `false` otherwise.
+function Vector.insert(self, index, value)
+end
+
+--- Removes the element at the given index, shifting the following elements.
+---@generic T
+---@param self Vector<T>
+---@param index integer The index of the element to remove.
+---@return boolean, T | nil -- The success flag and the removed value.
+function Vector.remove(self, index)
+end
+
+--- Removes the element at the given index, replacing it with the last element.
+---
+--- This does not preserve ordering, but does not shift the following elements.
+---@generic T
+---@param self Vector<T>
+---@param index integer The index of the element to remove.
+---@return boolean, T | nil -- The success flag and the removed value.
+function Vector.swap_remove(self, index)
+end
+
+--- Removes `count` elements starting at `index` and inserts the given values in their place.
+---
+--- `index` may also be `#self + 1` to splice at the end, and `count` is clamped to the
+--- number of available elements. Returns `false, nil` if the index is out of bounds or
+--- `count` is negative.
+---@generic T
+---@param self Vector<T>
+---@param index integer The index of the first element to remove.
+---@param count integer The number of elements to remove.
+---@param ... T The values to insert.
+---@return boolean, Vector<T> | nil -- The success flag and a vector with the removed elements.
+function Vector.splice(self, index, count, ...)
+end
+
+--- Appends every element of another vector, or of the sequence part of a table.
+---@generic T
+---@param self Vector<T>
+---@param other Vector<T> | T[] The elements to append.
+---@return boolean -- Returns `true` if the elements were appended.
+function Vector.extend(self, other)
+end
+
+--- Keeps only the first `len` elements. Has no effect if `len` is at least `#self`.
+---
+--- Causes an error if `len` is negative.
+---@generic T
+---@param self Vector<T>
+---@param len integer The new maximum length.
+function Vector.truncate(self, len)
+end
+
+--- Removes every element of the vector.
+---@generic T
+---@param self Vector<T>
+function Vector.clear(self)
+end
+
+--- Swaps the elements at two indices.
+---@generic T
+---@param self Vector<T>
+---@param i integer The index of the first element.
+---@param j integer The index of the second element.
+---@return boolean -- Returns `true` if both indices are within bounds, `false` otherwise.
+function Vector.swap(self, i, j)
+end
+
+--- Reverses the order of the elements in place.
+---@generic T
+---@param self Vector<T>
+function Vector.reverse(self)
+end
+
+--- Rotates the elements in place.
+---
+--- A positive `n` moves each element `n` positions towards the end, wrapping the last
+--- elements around to the front. A negative `n` rotates towards the start.
+---@generic T
+---@param self Vector<T>
+---@param n integer The number of positions to rotate by.
+function Vector.rotate(self, n)
+end
+
+--- Returns an iterator over the indices and values of the vector, from first to last.
+---
+--- Intended for a generic `for`: `for i, value in vec:iter() do ... end`. The iterator
+--- raises an error if the vector changes length while being traversed.
+---
+--- On Lua 5.2 and later, `pairs(vec)` returns this same iterator, and on every version
+--- `for i, value in vec do ... end` also works (without modification checks).
+---
+--- `ipairs(vec)` is only supported on Lua 5.2 and 5.3, which honour `__ipairs`. Elsewhere it
+--- raises an error: Lua 5.4 reads past the last element, which is out of bounds.
+---@generic T
+---@param self Vector<T>
+---@return fun(): integer, T -- The iterator function.
+function Vector.iter(self)
+end
+
+--- Returns an iterator over the indices and values of the vector, from last to first.
+---
+--- The yielded indices are the original ones. Like `iter`, it raises an error if the
+--- vector changes length while being traversed.
+---@generic T
+---@param self Vector<T>
+---@return fun(): integer, T -- The iterator function.
+function Vector.iter_rev(self)
+end
+
+--- Returns an iterator over the values of the vector, paired with a counter.
+---
+--- The counter starts at `start` and is incremented on every step. Like `iter`, it raises
+--- an error if the vector changes length while being traversed.
+---@generic T
+---@param self Vector<T>
+---@param start? integer The first counter value. Defaults to `1`.
+---@return fun(): integer, T -- The iterator function.
+function Vector.enumerate(self, start)
+end
+
+--- Creates a new vector of `f64` numbers with packed storage.
+---
+--- `vector.f32`, `vector.i64` and `vector.i32` create vectors of the corresponding element
+--- types. Typed vectors share the `get`, `set`, `push`, `pop`, indexing and length surface
+--- of `Vector`, but raise an error when a value that cannot be stored exactly is written
+--- (`f32` vectors round to the nearest `f32` instead, and only reject finite values beyond
+--- its range).
+---@param initial_size? integer The initial size of the vector.
+---@param filler_value? number The value used to fill the vector. Defaults to `0`.
+---@return TypedVector
+function vector.f64(initial_size, filler_value)
+end
+
+--- Creates a new vector of `f32` numbers with packed storage. See `vector.f64`.
+---@param initial_size? integer The initial size of the vector.
+---@param filler_value? number The value used to fill the vector. Defaults to `0`.
+---@return TypedVector
+function vector.f32(initial_size, filler_value)
+end
+
+--- Creates a new vector of `i64` integers with packed storage. See `vector.f64`.
+---@param initial_size? integer The initial size of the vector.
+---@param filler_value? integer The value used to fill the vector. Defaults to `0`.
+---@return TypedVector
+function vector.i64(initial_size, filler_value)
+end
+
+--- Creates a new vector of `i32` integers with packed storage. See `vector.f64`.
+---@param initial_size? integer The initial size of the vector.
+---@param filler_value? integer The value used to fill the vector. Defaults to `0`.
+---@return TypedVector
+function vector.i32(initial_size, filler_value)
+end
+
+--- Creates a new `f64` vector from a `Vector` or a table sequence.
+---
+--- `vector.f32_from`, `vector.i64_from` and `vector.i32_from` work the same way for their
+--- element types. Causes an error if any element cannot be stored exactly.
+---@param values Vector<number> | number[] The values to convert.
+---@return TypedVector
+function vector.f64_from(values)
+end
+
+--- Creates a new `f32` vector from a `Vector` or a table sequence. See `vector.f64_from`.
+---@param values Vector<number> | number[] The values to convert.
+---@return TypedVector
+function vector.f32_from(values)
+end
+
+--- Creates a new `i64` vector from a `Vector` or a table sequence. See `vector.f64_from`.
+---@param values Vector<integer> | integer[] The values to convert.
+---@return TypedVector
+function vector.i64_from(values)
+end
+
+--- Creates a new `i32` vector from a `Vector` or a table sequence. See `vector.f64_from`.
+---@param values Vector<integer> | integer[] The values to convert.
+---@return TypedVector
+function vector.i32_from(values)
+end
+
+---@class TypedVector A vector of numbers with packed storage, sharing the `Vector` access surface.
+local TypedVector = {}
+
+--- Retrieves an element, with the same presence flag as `Vector.get`.
+---@param self TypedVector
+---@param index integer The index of the element to retrieve.
+---@return boolean, number | nil -- A boolean flag indicating presence and the element's value.
+function TypedVector.get(self, index)
+end
+
+--- Sets a new value at a specific index.
+---
+--- Returns `false` if the index is out of bounds, and causes an error if the value cannot
+--- be stored exactly in the element type.
+---@param self TypedVector
+---@param index integer The index to set the value.
+---@param value number The new value.
+---@return boolean -- Returns `true` if the value was set, `false` otherwise.
+function TypedVector.set(self, index, value)
+end
+
+--- Adds a new element to the end of the vector.
+---
+--- Causes an error if the value cannot be stored exactly in the element type.
+---@param self TypedVector
+---@param value number The value to be pushed.
+---@return boolean -- Returns `true` if the operation was successful.
+function TypedVector.push(self, value)
+end
+
+--- Removes and returns the last element, along with a success flag.
+---@param self TypedVector
+---@return boolean, number | nil -- The success flag and the popped value.
+function TypedVector.pop(self)
+end
+
+--- Compares two typed vectors for reference equality.
+---@param self TypedVector
+---@param other any The other value to compare against.
+---@return boolean
+function TypedVector.is_same(self, other)
+end
+
+--- Converts the typed vector into a generic `Vector` holding the same values.
+---@param self TypedVector
+---@return Vector<number>
+function TypedVector.to_vector(self)
+end
+
+--- Returns the name of the element type: `"f64"`, `"f32"`, `"i64"` or `"i32"`.
+---@param self TypedVector
+---@return string
+function TypedVector.element_type(self)
+end
+
+--- Computes the dot product with another numeric vector of the same length.
+---
+--- Causes an error if the lengths differ or any element is not a number.
+---@param self Vector<number>
+---@param other Vector<number> The other vector.
+---@return number -- The dot product.
+function Vector.dot(self, other)
+end
+
+--- Computes the Euclidean norm (length) of a numeric vector.
+---@param self Vector<number>
+---@return number -- The norm.
+function Vector.norm(self)
+end
+
+--- Returns a new vector with the same direction and a norm of `1`.
+---
+--- Causes an error if the vector has zero norm.
+---@param self Vector<number>
+---@return Vector<number> -- The normalized vector.
+function Vector.normalize(self)
+end
+
+--- Computes the cross product with another numeric vector. Both vectors must have length 3.
+---@param self Vector<number>
+---@param other Vector<number> The other vector.
+---@return Vector<number> -- The cross product.
+function Vector.cross(self, other)
+end
+
+--- Adds up every element of a numeric vector. An empty vector sums to `0`.
+---@param self Vector<number>
+---@return number -- The sum.
+function Vector.sum(self)
+end
+
+--- Returns the smallest element of a numeric vector, or `nil` if it is empty.
+---@param self Vector<number>
+---@return number | nil -- The smallest element.
+function Vector.min(self)
+end
+
+--- Returns the largest element of a numeric vector, or `nil` if it is empty.
+---@param self Vector<number>
+---@return number | nil -- The largest element.
+function Vector.max(self)
+end
+
+--- Returns the arithmetic mean of a numeric vector, or `nil` if it is empty.
+---@param self Vector<number>
+---@return number | nil -- The mean.
+function Vector.mean(self)
+end
+
+--- Copies the elements of the vector into a new table.
+---
+--- This method returns two values: the table and the length of the vector, which tells
+--- where the sequence ends when it contains `nil` values.
+---@generic T
+---@param self Vector<T>
+---@return T[], integer -- The table and the number of elements.
+function Vector.to_table(self)
+end
+
+--- Returns the elements between `i` and `j`, inclusive, as multiple values.
+---
+--- The indices are interpreted like in `slice`.
+---@generic T
+---@param self Vector<T>
+---@param i? integer The first index. Defaults to `1`.
+---@param j? integer The last index. Defaults to `-1`.
+---@return T ...
+function Vector.unpack(self, i, j)
+end
+
+--- Encodes the vector as a JSON array.
+---
+--- `nil` elements are written as `null`. Nested vectors and sequence tables become arrays
+--- and other tables become objects. Causes an error for functions, userdata, cycles,
+--- strings that are not valid UTF-8 and non-finite numbers.
+---@param self Vector
+---@return string -- The JSON text.
+function Vector.to_json(self)
+end
+
+--- Decodes a JSON array into a new vector.
+---
+--- `null` elements are kept as present `nil` values, nested arrays become vectors and
+--- objects become tables. Causes an error if the text is not a JSON array.
+---@param json string The JSON text.
+---@return Vector
+function vector.from_json(json)
+end
+
+--- Encodes the vector as a MessagePack array.
+---
+--- It follows the same rules as `to_json`, but also supports binary strings and
+--- non-finite numbers.
+---@param self Vector
+---@return string -- The encoded bytes.
+function Vector.to_msgpack(self)
+end
+
+--- Decodes a MessagePack array, as produced by `to_msgpack`, into a new vector.
+---@param bytes string The encoded bytes.
+---@return Vector
+function vector.from_msgpack(bytes)
+end
+
+--- Creates a new empty vector able to hold `capacity` elements without reallocating.
+---
+--- Causes an error if the capacity is negative or too large to allocate.
+---@param capacity integer The number of elements to allocate room for.
+---@return Vector
+function vector.with_capacity(capacity)
+end
+
+--- Returns the number of elements the vector can hold without reallocating.
+---@param self Vector
+---@return integer -- The capacity.
+function Vector.capacity(self)
+end
+
+--- Reserves capacity for at least `additional` more elements.
+---
+--- Causes an error, leaving the vector unchanged, if `additional` is negative or the
+--- allocation is too large.
+---@param self Vector
+---@param additional integer The number of elements to allocate room for.
+function Vector.reserve(self, additional)
+end
+
+--- Releases the unused capacity of the vector.
+---@param self Vector
+function Vector.shrink_to_fit(self)
+end
+
+--- Creates a new double-ended queue holding the given elements, front to back.
+---@generic T
+---@param ... T The initial elements.
+---@return Deque<T>
+function vector.deque(...)
+end
+
+---@class Deque A double-ended queue with constant-time operations at both ends, sharing the `Vector` access surface.
+local Deque = {}
+
+--- Retrieves an element, with the same presence flag as `Vector.get`.
+---@generic T
+---@param self Deque<T>
+---@param index integer The index of the element to retrieve, counted from the front.
+---@return boolean, T | nil -- A boolean flag indicating presence and the element's value.
+function Deque.get(self, index)
+end
+
+--- Sets a new value at a specific index. Returns `false` if the index is out of bounds.
+---@generic T
+---@param self Deque<T>
+---@param index integer The index of the element to set.
+---@param value T The new value.
+---@return boolean -- `true` on success.
+function Deque.set(self, index, value)
+end
+
+--- Appends a value to the back of the deque.
+---@generic T
+---@param self Deque<T>
+---@param value T The value to append.
+---@return boolean -- `true` on success.
+function Deque.push(self, value)
+end
+
+--- Prepends a value to the front of the deque.
+---@generic T
+---@param self Deque<T>
+---@param value T The value to prepend.
+---@return boolean -- `true` on success.
+function Deque.push_front(self, value)
+end
+
+--- Removes and returns the last element, along with a presence flag.
+---@generic T
+---@param self Deque<T>
+---@return boolean, T | nil
+function Deque.pop(self)
+end
+
+--- Removes and returns the first element, along with a presence flag.
+---@generic T
+---@param self Deque<T>
+---@return boolean, T | nil
+function Deque.pop_front(self)
+end
+
+--- Returns the first element without removing it, along with a presence flag.
+---@generic T
+---@param self Deque<T>
+---@return boolean, T | nil
+function Deque.peek_front(self)
+end
+
+--- Returns the last element without removing it, along with a presence flag.
+---@generic T
+---@param self Deque<T>
+---@return boolean, T | nil
+function Deque.peek_back(self)
+end
+
+--- Checks if two deques are the same instance.
+---@param self Deque
+---@param other any The value to compare with.
+---@return boolean
+function Deque.is_same(self, other)
+end
+
+--- Copies the elements, front to back, into a new vector.
+---@generic T
+---@param self Deque<T>
+---@return Vector<T>
+function Deque.to_vector(self)
+end
+
+--- Creates a new empty ring buffer holding at most `capacity` elements.
+---
+--- Once the buffer is full, pushing a new element evicts the oldest one. Causes an error
+--- if the capacity is not a positive number.
+---@param capacity integer The maximum number of elements.
+---@return Ring
+function vector.ring(capacity)
+end
+
+---@class Ring A fixed-capacity buffer that overwrites its oldest elements, indexed from the oldest one.
+local Ring = {}
+
+--- Retrieves an element, with the same presence flag as `Vector.get`.
+---
+--- Index `1` is the oldest element and `-1` the newest one.
+---@generic T
+---@param self Ring<T>
+---@param index integer The index of the element to retrieve.
+---@return boolean, T | nil -- A boolean flag indicating presence and the element's value.
+function Ring.get(self, index)
+end
+
+--- Sets a new value at a specific index. Returns `false` if the index is out of bounds.
+---@generic T
+---@param self Ring<T>
+---@param index integer The index of the element to set.
+---@param value T The new value.
+---@return boolean -- `true` on success.
+function Ring.set(self, index, value)
+end
+
+--- Appends a value as the newest element.
+---
+--- If the buffer was full, the oldest element is evicted and returned along with `true`.
+--- Otherwise, it returns `false` and `nil`.
+---@generic T
+---@param self Ring<T>
+---@param value T The value to append.
+---@return boolean, T | nil -- Whether an element was evicted, and the evicted element.
+function Ring.push(self, value)
+end
+
+--- Checks if the next push will evict the oldest element.
+---@param self Ring
+---@return boolean
+function Ring.is_full(self)
+end
+
+--- Returns the maximum number of elements held by the buffer.
+---@param self Ring
+---@return integer
+function Ring.capacity(self)
+end
+
+--- Removes all the elements, keeping the capacity.
+---@param self Ring
+function Ring.clear(self)
+end
+
+--- Returns an iterator over the elements, from the oldest to the newest.
+---
+--- Like `Vector.iter`, the iterator raises an error if the buffer is modified during
+--- the traversal.
+---@generic T
+---@param self Ring<T>
+---@return fun(): integer, T
+function Ring.iter(self)
+end
+
+--- Copies the elements, from the oldest to the newest, into a new vector.
+---@generic T
+---@param self Ring<T>
+---@return Vector<T>
+function Ring.to_vector(self)
+end
+
+--- Compares the vector with another value by content, recursively.
+---
+--- Unlike `==`, which compares nested tables and vectors by reference, nested vectors are
+--- equal if their elements are deeply equal, and tables if they have the same keys mapped
+--- to deeply equal values. Cyclic structures are supported.
+---@param self Vector
+---@param other any The value to compare with.
+---@return boolean
+function Vector.deep_equals(self, other)
+end
+
+--- Checks whether an element is equal to `value`, using the same equality as `==`.
+---@generic T
+---@param self Vector<T>
+---@param value T The value to look for.
+---@return boolean
+function Vector.contains(self, value)
+end
+
+--- Returns the index of the first element equal to `value`, or `nil` if there is none.
+---
+--- The search starts at `start`, which is interpreted like the indices of `slice`.
+---@generic T
+---@param self Vector<T>
+---@param value T The value to look for.
+---@param start? integer The index to start searching from. Defaults to `1`.
+---@return integer | nil -- The index of the element.
+function Vector.index_of(self, value, start)
+end
+
+--- Returns the index of the last element equal to `value`, or `nil` if there is none.
+---@generic T
+---@param self Vector<T>
+---@param value T The value to look for.
+---@return integer | nil -- The index of the element.
+function Vector.last_index_of(self, value)
+end
+
+--- Counts the elements equal to a value, or accepted by a predicate.
+---
+--- When given a function, it counts the elements for which `pred(value, index)` is truthy.
+---@generic T
+---@param self Vector<T>
+---@param value_or_pred T | fun(value: T, index: integer): boolean The value or predicate.
+---@return integer -- The number of matching elements.
+function Vector.count(self, value_or_pred)
+end
+
+--- Returns a new vector keeping only the first occurrence of every element.
+---@generic T
+---@param self Vector<T>
+---@return Vector<T>
+function Vector.unique(self)
+end
+
+--- Removes consecutive repeated elements in place, keeping the first of each run.
+---@param self Vector
+function Vector.dedup(self)
+end
+
+--- Creates a new frozen vector from a list of elements.
+---@generic T
+---@param ... T The elements of the vector.
+---@return Vector<T>
+function vector.frozen_of(...)
+end
+
+--- Makes the vector immutable.
+---
+--- Every mutator called afterwards, including `v[i] = x` and writes through views, raises
+--- an error. Freezing cannot be undone, but `thaw_copy` returns a mutable copy.
+---@param self Vector
+function Vector.freeze(self)
+end
+
+--- Checks whether the vector has been frozen.
+---@param self Vector
+---@return boolean
+function Vector.is_frozen(self)
+end
+
+--- Returns a mutable copy of the vector, whether it is frozen or not.
+---@generic T
+---@param self Vector<T>
+---@return Vector<T>
+function Vector.thaw_copy(self)
+end
+
+--- Creates a new persistent vector, optionally holding the elements of a vector or table.
+---
+--- Persistent vectors are immutable: `push`, `set` and `pop` return new versions in
+--- `O(log n)`, sharing their unchanged structure with the previous versions.
+---@generic T
+---@param values? Vector<T> | T[] The initial elements.
+---@return PersistentVector<T>
+function vector.persistent(values)
+end
+
+--- Creates a persistent vector holding a copy of the elements.
+---@generic T
+---@param self Vector<T>
+---@return PersistentVector<T>
+function Vector.to_persistent(self)
+end
+
+---@class PersistentVector An immutable vector whose updates return new versions, sharing the `Vector` read surface.
+local PersistentVector = {}
+
+--- Retrieves an element, with the same presence flag as `Vector.get`.
+---@generic T
+---@param self PersistentVector<T>
+---@param index integer The index of the element to retrieve.
+---@return boolean, T | nil -- A boolean flag indicating presence and the element's value.
+function PersistentVector.get(self, index)
+end
+
+--- Returns a new version with `value` appended, leaving this one unchanged.
+---@generic T
+---@param self PersistentVector<T>
+---@param value T The value to append.
+---@return PersistentVector<T>
+function PersistentVector.push(self, value)
+end
+
+--- Returns a new version with the element at `index` replaced, leaving this one unchanged.
+---
+--- Causes an error if the index is out of bounds.
+---@generic T
+---@param self PersistentVector<T>
+---@param index integer The index of the element to replace.
+---@param value T The new value.
+---@return PersistentVector<T>
+function PersistentVector.set(self, index, value)
+end
+
+--- Returns a presence flag, the last element and a new version without it.
+---
+--- On an empty vector, it returns `false`, `nil` and the vector itself.
+---@generic T
+---@param self PersistentVector<T>
+---@return boolean, T | nil, PersistentVector<T>
+function PersistentVector.pop(self)
+end
+
+--- Copies the elements into a new, mutable vector.
+---@generic T
+---@param self PersistentVector<T>
+---@return Vector<T>
+function PersistentVector.to_vector(self)
+end
+
+--- Creates a lazy iterator over the elements.
+---
+--- Combinators build a pipeline without calling any callback. Items are only computed
+--- when a terminal operation (`collect`, `fold`, `count`, `first`, `last`) runs. Like
+--- `Vector.iter`, the pipeline raises an error if the vector is modified meanwhile.
+---@generic T
+---@param self Vector<T>
+---@return Lazy
+function Vector.lazy(self)
+end
+
+---@class Lazy A single-use lazy iterator. Every method consumes it, and reusing it raises an error.
+local Lazy = {}
+
+--- Replaces every item with the result of `func(...)`.
+---@param self Lazy
+---@param func fun(...): any
+---@return Lazy
+function Lazy.map(self, func)
+end
+
+--- Keeps the items for which `pred(...)` is truthy.
+---@param self Lazy
+---@param pred fun(...): boolean
+---@return Lazy
+function Lazy.filter(self, pred)
+end
+
+--- Keeps at most `n` items.
+---@param self Lazy
+---@param n integer
+---@return Lazy
+function Lazy.take(self, n)
+end
+
+--- Discards the first `n` items.
+---@param self Lazy
+---@param n integer
+---@return Lazy
+function Lazy.skip(self, n)
+end
+
+--- Keeps items until `pred(...)` is falsy for the first time.
+---@param self Lazy
+---@param pred fun(...): boolean
+---@return Lazy
+function Lazy.take_while(self, pred)
+end
+
+--- Discards items until `pred(...)` is falsy for the first time.
+---@param self Lazy
+---@param pred fun(...): boolean
+---@return Lazy
+function Lazy.skip_while(self, pred)
+end
+
+--- Pairs every item with the next one of `other`, passing both to later callbacks.
+---
+--- Stops when either side is exhausted. A `Lazy` argument is consumed.
+---@param self Lazy
+---@param other Lazy | Vector | any[]
+---@return Lazy
+function Lazy.zip(self, other)
+end
+
+--- Yields the items of this iterator, then those of `other`. A `Lazy` argument is consumed.
+---@param self Lazy
+---@param other Lazy | Vector | any[]
+---@return Lazy
+function Lazy.chain(self, other)
+end
+
+--- Prepends a counter, starting at 1, to every item.
+---@param self Lazy
+---@return Lazy
+function Lazy.enumerate(self)
+end
+
+--- Keeps the first item and then every `step`-th one.
+---@param self Lazy
+---@param step integer A positive step.
+---@return Lazy
+function Lazy.step_by(self, step)
+end
+
+--- Collects the items into a new vector. Items with several values become nested vectors.
+---@param self Lazy
+---@return Vector
+function Lazy.collect(self)
+end
+
+--- Calls `func(acc, ...)` for every item and returns the final accumulator.
+---@generic A
+---@param self Lazy
+---@param func fun(acc: A, ...): A
+---@param init A
+---@return A
+function Lazy.fold(self, func, init)
+end
+
+--- Returns the number of items.
+---@param self Lazy
+---@return integer
+function Lazy.count(self)
+end
+
+--- Returns `true` and the values of the first item, or `false` if there is none.
+---@param self Lazy
+---@return boolean, ...
+function Lazy.first(self)
+end
+
+--- Returns `true` and the values of the last item, or `false` if there is none.
+---@param self Lazy
+---@return boolean, ...
+function Lazy.last(self)
+end
+
+--- Splits the vector into consecutive chunks of `size` elements, the last one possibly shorter.
+---
+--- Causes an error if `size` is not positive.
+---@generic T
+---@param self Vector<T>
+---@param size integer The number of elements per chunk.
+---@return Vector<Vector<T>>
+function Vector.chunks(self, size)
+end
+
+--- Returns every overlapping window of `size` elements, in order.
+---
+--- A vector shorter than `size` has no windows. Causes an error if `size` is not positive.
+---@generic T
+---@param self Vector<T>
+---@param size integer The number of elements per window.
+---@return Vector<Vector<T>>
+function Vector.windows(self, size)
+end
+
+--- Groups the elements by the key returned by `key_fn(value, index)`.
+---
+--- Keys are compared with raw equality, and `nil` or NaN keys cause an error.
+---@generic T, K
+---@param self Vector<T>
+---@param key_fn fun(value: T, index: integer): K
+---@return table<K, Vector<T>> -- A table mapping every key to the elements that produced it.
+function Vector.group_by(self, key_fn)
+end
+
+--- Splits the vector into the elements accepted by `pred(value, index)` and the others.
+---@generic T
+---@param self Vector<T>
+---@param pred fun(value: T, index: integer): boolean
+---@return Vector<T>, Vector<T>
+function Vector.partition(self, pred)
+end
+
+--- Pairs every element with the element at the same position in `other`.
+---
+--- The result is as long as the shorter of the two.
+---@generic T, U
+---@param self Vector<T>
+---@param other Vector<U> | U[]
+---@return Vector<Vector> -- A vector of two-element vectors.
+function Vector.zip(self, other)
+end
+
+--- Splits a vector of pairs into the vector of first elements and the vector of second elements.
+---
+--- Every element must be a two-element vector or table.
+---@param self Vector<Vector | any[]>
+---@return Vector, Vector
+function Vector.unzip(self)
+end
+
+--- Creates a new grid of `rows` by `cols` cells, all set to `filler`.
+---
+--- Rows and columns follow the `Vector` indexing conventions independently: they start
+--- at 1, and negative values count from the last row or column.
+---@generic T
+---@param rows integer The number of rows.
+---@param cols integer The number of columns.
+---@param filler? T The initial value of every cell.
+---@return Grid<T>
+function vector.grid(rows, cols, filler)
+end
+
+--- Creates the `n` by `n` identity matrix, with integer cells.
+---@param n integer The size of the matrix.
+---@return Grid<integer>
+function vector.identity(n)
+end
+
+---@class Grid A fixed-size two-dimensional grid of values, stored row by row.
+local Grid = {}
+
+--- Retrieves a cell, with the same presence flag as `Vector.get`.
+---@generic T
+---@param self Grid<T>
+---@param row integer
+---@param col integer
+---@return boolean, T | nil -- A boolean flag indicating presence and the cell's value.
+function Grid.get(self, row, col)
+end
+
+--- Sets a cell. Returns `false` if the position is out of bounds, like `Vector.set`.
+---@generic T
+---@param self Grid<T>
+---@param row integer
+---@param col integer
+---@param value T
+---@return boolean
+function Grid.set(self, row, col, value)
+end
+
+--- Returns the number of rows.
+---@param self Grid
+---@return integer
+function Grid.rows(self)
+end
+
+--- Returns the number of columns.
+---@param self Grid
+---@return integer
+function Grid.cols(self)
+end
+
+--- Returns a new vector holding the cells of a row. Causes an error if it is out of bounds.
+---@generic T
+---@param self Grid<T>
+---@param row integer
+---@return Vector<T>
+function Grid.row(self, row)
+end
+
+--- Returns a new vector holding the cells of a column. Causes an error if it is out of bounds.
+---@generic T
+---@param self Grid<T>
+---@param col integer
+---@return Vector<T>
+function Grid.column(self, col)
+end
+
+--- Returns a new grid whose rows are the columns of this one.
+---@generic T
+---@param self Grid<T>
+---@return Grid<T>
+function Grid.transpose(self)
+end
+
+--- Replaces the region connected to a cell with `value`.
+---
+--- The region holds the cell and every cell reachable through orthogonal neighbours equal
+--- to its original value. Causes an error if the position is out of bounds.
+---@generic T
+---@param self Grid<T>
+---@param row integer
+---@param col integer
+---@param value T
+---@return integer -- The number of cells filled.
+function Grid.flood_fill(self, row, col, value)
+end
+
+--- Returns an iterator over the neighbours of a cell, yielding their row, column and value.
+---
+--- Only orthogonal neighbours are included unless `diagonal` is `true`. Positions outside
+--- the grid are skipped. Causes an error if the cell itself is out of bounds.
+---@generic T
+---@param self Grid<T>
+---@param row integer
+---@param col integer
+---@param diagonal? boolean
+---@return fun(): integer, integer, T
+function Grid.neighbours(self, row, col, diagonal)
+end
+
+--- Returns the matrix product of this grid by `other`.
+---
+--- Causes an error if the columns of this grid do not match the rows of `other`, or if a
+--- cell is not a number.
+---@param self Grid<number>
+---@param other Grid<number>
+---@return Grid<number>
+function Grid.matmul(self, other)
+end
+
+--- Creates a byte buffer, either holding `n` zero bytes or a copy of the bytes of a string.
+---
+--- Bytes are integers from 0 to 255, indexed like the elements of a `Vector`.
+---@param source integer | string The length of the buffer, or the string to copy.
+---@return Bytes
+function vector.bytes(source)
+end
+
+--- Creates a byte buffer from a hexadecimal string. Causes an error on invalid input.
+---@param text string
+---@return Bytes
+function vector.bytes_from_hex(text)
+end
+
+--- Creates a byte buffer from a padded base64 string. Causes an error on invalid input.
+---@param text string
+---@return Bytes
+function vector.bytes_from_base64(text)
+end
+
+---@class Bytes A compact buffer of bytes with binary readers and writers.
+---
+--- Numbers are read and written at a 1-based offset with `read_<type>` and `write_<type>`,
+--- where `<type>` is `u8`, `i8`, or one of `u16`, `i16`, `u32`, `i32`, `i64`, `f32`, `f64`
+--- followed by the byte order `le` or `be`, as in `read_u16le` or `write_f64be`. Reads
+--- and writes past the end of the buffer, and values an integer type cannot represent
+--- exactly, cause an error. `f32` and `f64` values are rounded like `string.pack` does.
+local Bytes = {}
+
+--- Retrieves a byte, with the same presence flag as `Vector.get`.
+---@param self Bytes
+---@param index integer
+---@return boolean, integer | nil
+function Bytes.get(self, index)
+end
+
+--- Sets a byte. Returns `false` if the index is out of bounds, like `Vector.set`.
+---
+--- Causes an error if the value is not an integer from 0 to 255.
+---@param self Bytes
+---@param index integer
+---@param byte integer
+---@return boolean
+function Bytes.set(self, index, byte)
+end
+
+--- Appends a byte. Causes an error if the value is not an integer from 0 to 255.
+---@param self Bytes
+---@param byte integer
+---@return boolean
+function Bytes.push(self, byte)
+end
+
+--- Returns the bytes as a Lua string.
+---@param self Bytes
+---@return string
+function Bytes.to_string(self)
+end
+
+--- Encodes the bytes as lower case hexadecimal.
+---@param self Bytes
+---@return string
+function Bytes.to_hex(self)
+end
+
+--- Encodes the bytes as padded base64.
+---@param self Bytes
+---@return string
+function Bytes.to_base64(self)
+end
+
+--- Subscribes `func` to the changes made to the vector, returning a subscription handle.
+---
+--- `func(op, index, old, new)` is called after every change, with the name of the
+--- mutator (such as `"set"`, `"push"` or `"__newindex"`). For changes to a single element,
+--- `index` is its position and `old` and `new` its values, `nil` when the element was added
+--- or removed; `swap` and `swap_remove` call `func` once per position they touch. Changes
+--- to several elements at once, such as `sort`, pass `nil` for the three. Every observer runs even if one fails; the error is then raised by the mutator,
+--- which has already completed.
+---@generic T
+---@param self Vector<T>
+---@param func fun(op: string, index: integer | nil, old: T | nil, new: T | nil)
+---@return Subscription
+function Vector.on_change(self, func)
+end
+
+--- Calls `func(self)`, notifying the observers a single time once it returns.
+---
+--- The notification uses the `"batch"` operation, and is only sent if `func` changed the
+--- vector. Nested batches notify when the outermost one ends. Errors raised by `func` are
+--- propagated after the notification. Returns the results of `func`.
+---@generic T
+---@param self Vector<T>
+---@param func fun(vec: Vector<T>): ...
+---@return ...
+function Vector.batch(self, func)
+end
+
+---@class Subscription The handle of a `Vector.on_change` subscription.
+local Subscription = {}
+
+--- Stops the notifications. Returns `false` if the subscription was already cancelled.
+---@param self Subscription
+---@return boolean
+function Subscription.unsubscribe(self)
+end
+
+--- Returns `true` until the subscription is cancelled.
+---@param self Subscription
+---@return boolean
+function Subscription.is_active(self)
+end
+
+--- Starts recording the mutations of the vector, so that they can be reverted with `undo`.
+---
+--- Each mutator call is one undo step, and a `batch` groups the changes it makes into a
+--- single step. At most `limit` steps are kept, 100 by default; calling it again changes
+--- the limit while keeping the recorded steps.
+---@param self Vector
+---@param limit? integer
+function Vector.track_history(self, limit)
+end
+
+--- Reverts the latest undo step. Returns `false` if there is nothing to undo or the
+--- history is not tracked. Causes an error inside a `batch`.
+---@param self Vector
+---@return boolean
+function Vector.undo(self)
+end
+
+--- Reapplies the latest undone step. Returns `false` if there is nothing to redo: any
+--- mutation made after `undo` discards the undone steps. Causes an error inside a `batch`.
+---@param self Vector
+---@return boolean
+function Vector.redo(self)
+end
+
+--- Returns an immutable, shallow copy of the elements, to be passed to `restore`.
+---@generic T
+---@param self Vector<T>
+---@return Snapshot<T>
+function Vector.snapshot(self)
+end
+
+--- Replaces the elements with the ones of a snapshot, as a single undo step.
+---@generic T
+---@param self Vector<T>
+---@param snapshot Snapshot<T>
+function Vector.restore(self, snapshot)
+end
+
+---@class Snapshot<T> An immutable copy of the elements of a `Vector`.
+---@operator len: integer
+local Snapshot = {}
+
+--- Returns a new `Vector` holding the elements of the snapshot.
+---@generic T
+---@param self Snapshot<T>
+---@return Vector<T>
+function Snapshot.to_vector(self)
+end
+
 vector = require("vector.core")
 
 return vector
\ No newline at end of file
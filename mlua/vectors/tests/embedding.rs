@@ -0,0 +1,87 @@
+//! Tests of the Rust API offered to embedding hosts.
+//!
+//! They create their own Lua states, so they need Lua linked into the test binary and
+//! only build without the `module` feature. Run them with `cargo test-embedding`, an
+//! alias for `cargo test --no-default-features --features lua54,vendored --test embedding`.
+#![cfg(not(feature = "module"))]
+
+use mlua::prelude::*;
+use vectors::{LuaVec, register, with_values};
+
+/// Creates a Lua state with the module registered.
+fn lua_with_module() -> LuaResult<Lua> {
+    let lua = Lua::new();
+    register(&lua)?;
+    Ok(lua)
+}
+
+#[test]
+fn register_makes_the_module_available_to_require() -> LuaResult<()> {
+    let lua = Lua::new();
+    let module = register(&lua)?;
+
+    let required: LuaTable = lua.load(r#"return require("vector")"#).eval()?;
+    assert_eq!(required, module);
+
+    let len: LuaInteger = lua
+        .load(r#"return #require("vector").of(1, 2, 3)"#)
+        .eval()?;
+    assert_eq!(len, 3);
+    Ok(())
+}
+
+#[test]
+fn vec_round_trips_through_a_vector() -> LuaResult<()> {
+    let lua = lua_with_module()?;
+    let push: LuaFunction = lua
+        .load("return function(vec) vec:push(4); return vec, getmetatable(vec) ~= nil end")
+        .eval()?;
+
+    let (values, is_userdata): (LuaVec<i64>, bool) = push.call(LuaVec(vec![1, 2, 3]))?;
+    assert!(is_userdata);
+    assert_eq!(values.into_inner(), vec![1, 2, 3, 4]);
+    Ok(())
+}
+
+#[test]
+fn vec_reports_elements_of_the_wrong_type() -> LuaResult<()> {
+    let lua = lua_with_module()?;
+
+    let result = lua
+        .load(r#"return require("vector").of(1, "two")"#)
+        .eval::<LuaVec<i64>>();
+    assert!(result.is_err());
+
+    let result = lua.load("return 42").eval::<LuaVec<i64>>();
+    assert!(matches!(
+        result,
+        Err(LuaError::FromLuaConversionError { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn with_values_borrows_the_elements_of_a_vector() -> LuaResult<()> {
+    let lua = lua_with_module()?;
+    let vec: LuaValue = lua.load(r#"return require("vector").of(1, 2, 3)"#).eval()?;
+
+    let len = with_values(&vec, |values| values.len())?;
+    assert_eq!(len, 3);
+    Ok(())
+}
+
+#[test]
+fn with_values_rejects_values_that_are_not_vectors() -> LuaResult<()> {
+    let lua = lua_with_module()?;
+    let table: LuaValue = lua.load("return { 1, 2, 3 }").eval()?;
+
+    let err = with_values(&table, |values| values.len()).unwrap_err();
+    assert!(err.to_string().contains("Expected a Vector, got table."));
+
+    let grid: LuaValue = lua
+        .load(r#"return require("vector").grid(2, 2, 0)"#)
+        .eval()?;
+    let err = with_values(&grid, |values| values.len()).unwrap_err();
+    assert!(err.to_string().contains("Expected a Vector, got userdata."));
+    Ok(())
+}